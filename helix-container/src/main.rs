@@ -0,0 +1,78 @@
+use helix_db::helix_engine::graph_core::HelixGraphEngine;
+
+/// Env var overriding the on-disk data directory. Falls back to the fixed
+/// default [`DEFAULT_DATA_DIR`] rather than a fresh `{Utc::now()}`-named
+/// directory every start — the previous behavior meant the graph was never
+/// actually persisted across restarts, just recreated empty each time.
+const DATA_DIR_ENV: &str = "HELIX_DATA_DIR";
+
+/// Default data directory when neither `--data-dir` nor `HELIX_DATA_DIR`
+/// is given. Fixed across restarts so the store at this path is reused,
+/// not recreated.
+const DEFAULT_DATA_DIR: &str = "./graph_data";
+
+/// Env var that, when set to anything, wipes the resolved data directory
+/// before opening it — the explicit opt-in a caller who actually wants a
+/// fresh, empty database now has to ask for, instead of getting one on
+/// every start whether they wanted it or not.
+const FRESH_START_ENV: &str = "HELIX_FRESH_START";
+
+/// Resolves the directory HelixDB stores its data in: `--data-dir <path>`
+/// if given, else `$HELIX_DATA_DIR`, else the fixed [`DEFAULT_DATA_DIR`].
+fn resolve_data_dir(args: &[String]) -> String {
+    if let Some(pos) = args.iter().position(|a| a == "--data-dir") {
+        if let Some(path) = args.get(pos + 1) {
+            return path.clone();
+        }
+    }
+    std::env::var(DATA_DIR_ENV).unwrap_or_else(|_| DEFAULT_DATA_DIR.to_string())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let data_dir = resolve_data_dir(&args);
+
+    if std::env::var(FRESH_START_ENV).is_ok() {
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    let engine = HelixGraphEngine::new(&data_dir).expect("failed to open graph storage");
+    println!("HelixDB opened at {data_dir}");
+    drop(engine);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_db::helix_engine::storage_core::StorageMethods;
+
+    #[test]
+    fn resolve_data_dir_prefers_the_cli_flag_over_the_env_var_over_the_default() {
+        assert_eq!(resolve_data_dir(&[]), DEFAULT_DATA_DIR);
+
+        let with_flag = vec!["helix-container".to_string(), "--data-dir".to_string(), "/tmp/from-flag".to_string()];
+        assert_eq!(resolve_data_dir(&with_flag), "/tmp/from-flag");
+
+        std::env::set_var(DATA_DIR_ENV, "/tmp/from-env");
+        assert_eq!(resolve_data_dir(&[]), "/tmp/from-env");
+        assert_eq!(resolve_data_dir(&with_flag), "/tmp/from-flag");
+        std::env::remove_var(DATA_DIR_ENV);
+    }
+
+    #[test]
+    fn a_second_start_on_the_same_path_sees_the_first_runs_data() {
+        let dir = std::env::temp_dir().join(format!("helix-container-test-{}", uuid::Uuid::new_v4()));
+        let path = dir.to_str().unwrap().to_string();
+
+        {
+            let engine = HelixGraphEngine::new(&path).unwrap();
+            engine.create_node("person", Default::default()).unwrap();
+        }
+        {
+            // Reopening the same stable path (not a fresh `{Utc::now()}`
+            // directory) must see the node the first run created.
+            let engine = HelixGraphEngine::new(&path).unwrap();
+            assert_eq!(engine.storage.get_all_nodes().unwrap().len(), 1);
+        }
+    }
+}
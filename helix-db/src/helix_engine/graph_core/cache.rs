@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Config for [`HelixGraphEngine`](super::HelixGraphEngine)'s query-result
+/// cache. Disabled by default — callers opt in explicitly.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub ttl: Duration,
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            enabled: false,
+            ttl: Duration::from_secs(30),
+            max_entries: 256,
+        }
+    }
+}
+
+struct CacheEntry {
+    body: Vec<u8>,
+    inserted_at: Instant,
+    generation: u64,
+}
+
+/// Caches serialized query results keyed by a normalized query string.
+///
+/// Invalidation is coarse: every entry records the generation counter at
+/// insertion time, and [`bump_generation`](QueryCache::bump_generation) (called
+/// on every write) makes all older entries invisible without having to track
+/// per-query dependencies. Entries also expire on their own after `ttl`.
+pub struct QueryCache {
+    config: CacheConfig,
+    generation: AtomicU64,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl QueryCache {
+    pub fn new(config: CacheConfig) -> Self {
+        QueryCache {
+            config,
+            generation: AtomicU64::new(0),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached body for `key` if present, not expired, and not
+    /// older than the current generation.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        if !self.config.enabled {
+            return None;
+        }
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        if entry.generation != self.generation.load(Ordering::SeqCst) {
+            return None;
+        }
+        if entry.inserted_at.elapsed() > self.config.ttl {
+            return None;
+        }
+        Some(entry.body.clone())
+    }
+
+    pub fn put(&self, key: String, body: Vec<u8>) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            // Coarse eviction: drop an arbitrary entry rather than tracking
+            // recency, since entries are already cheaply recomputed.
+            if let Some(evict_key) = entries.keys().next().cloned() {
+                entries.remove(&evict_key);
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                body,
+                inserted_at: Instant::now(),
+                generation: self.generation.load(Ordering::SeqCst),
+            },
+        );
+    }
+
+    /// Invalidates every cached entry. Called on any write.
+    pub fn bump_generation(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,245 @@
+//! A tiny expression evaluator for the filter string a parsed `WHERE`
+//! clause lowers to, used by [`super::traversal::TraversalBuilder::filter_expr`].
+
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{Node, Value};
+use std::cmp::Ordering;
+
+/// Evaluates `expr` against `node`'s properties.
+///
+/// Grammar: `or_expr := and_expr ('||' and_expr)*`, `and_expr := comparison
+/// ('&&' comparison)*`, `comparison := key op literal` with `op` one of
+/// `== != > < >= <=` and `literal` a quoted string, an integer, a float, or
+/// `true`/`false`. `key` is resolved against `node.properties`; a missing
+/// key makes `==`/`>`/`<`/`>=`/`<=` evaluate to `false` and `!=` evaluate to
+/// `true`, the same "absent means doesn't match" convention
+/// [`Value::compare`]-based steps like `where_gt` already use.
+pub fn eval_filter(expr: &str, node: &Node) -> Result<bool, GraphError> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+    let result = parser.parse_or(node)?;
+    if parser.pos != parser.tokens.len() {
+        return Err(GraphError::New(format!(
+            "unexpected trailing tokens in filter expression {expr:?}"
+        )));
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Op(String),
+    Literal(Value),
+    And,
+    Or,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, GraphError> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '&' && chars.get(i + 1) == Some(&'&') {
+            tokens.push(Token::And);
+            i += 2;
+            continue;
+        }
+        if c == '|' && chars.get(i + 1) == Some(&'|') {
+            tokens.push(Token::Or);
+            i += 2;
+            continue;
+        }
+        if c == '=' || c == '!' || c == '>' || c == '<' {
+            if chars.get(i + 1) == Some(&'=') {
+                tokens.push(Token::Op(format!("{c}=")));
+                i += 2;
+            } else if c == '=' {
+                return Err(GraphError::New(format!(
+                    "invalid operator '=' in filter expression {expr:?} (did you mean '=='?)"
+                )));
+            } else {
+                tokens.push(Token::Op(c.to_string()));
+                i += 1;
+            }
+            continue;
+        }
+        if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(GraphError::New(format!(
+                    "unterminated string literal in filter expression {expr:?}"
+                )));
+            }
+            i += 1;
+            tokens.push(Token::Literal(Value::String(s)));
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = i;
+            let mut is_float = false;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                is_float |= chars[i] == '.';
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let literal = if is_float {
+                Value::Float(text.parse().map_err(|_| {
+                    GraphError::New(format!("invalid number literal {text:?} in filter expression {expr:?}"))
+                })?)
+            } else {
+                Value::Integer(text.parse().map_err(|_| {
+                    GraphError::New(format!("invalid number literal {text:?} in filter expression {expr:?}"))
+                })?)
+            };
+            tokens.push(Token::Literal(literal));
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(match text.as_str() {
+                "true" => Token::Literal(Value::Boolean(true)),
+                "false" => Token::Literal(Value::Boolean(false)),
+                _ => Token::Ident(text),
+            });
+            continue;
+        }
+        return Err(GraphError::New(format!(
+            "unexpected character {c:?} in filter expression {expr:?}"
+        )));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_or(&mut self, node: &Node) -> Result<bool, GraphError> {
+        let mut result = self.parse_and(node)?;
+        while matches!(self.tokens.get(self.pos), Some(Token::Or)) {
+            self.pos += 1;
+            let rhs = self.parse_and(node)?;
+            result = result || rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self, node: &Node) -> Result<bool, GraphError> {
+        let mut result = self.parse_comparison(node)?;
+        while matches!(self.tokens.get(self.pos), Some(Token::And)) {
+            self.pos += 1;
+            let rhs = self.parse_comparison(node)?;
+            result = result && rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_comparison(&mut self, node: &Node) -> Result<bool, GraphError> {
+        let key = match self.tokens.get(self.pos) {
+            Some(Token::Ident(k)) => k.clone(),
+            other => return Err(GraphError::New(format!("expected a property name, got {other:?}"))),
+        };
+        self.pos += 1;
+
+        let op = match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => op.clone(),
+            other => {
+                return Err(GraphError::New(format!(
+                    "expected a comparison operator after {key:?}, got {other:?}"
+                )))
+            }
+        };
+        self.pos += 1;
+
+        let literal = match self.tokens.get(self.pos) {
+            Some(Token::Literal(v)) => v.clone(),
+            other => {
+                return Err(GraphError::New(format!(
+                    "expected a literal after {key:?} {op}, got {other:?}"
+                )))
+            }
+        };
+        self.pos += 1;
+
+        let property = node.properties.get(&key);
+        Ok(match op.as_str() {
+            "==" => property.map_or(false, |p| *p == literal),
+            "!=" => property.map_or(true, |p| *p != literal),
+            ">" => property
+                .and_then(|p| p.compare(&literal))
+                .map_or(false, |ord| ord == Ordering::Greater),
+            "<" => property
+                .and_then(|p| p.compare(&literal))
+                .map_or(false, |ord| ord == Ordering::Less),
+            ">=" => property
+                .and_then(|p| p.compare(&literal))
+                .map_or(false, |ord| ord != Ordering::Less),
+            "<=" => property
+                .and_then(|p| p.compare(&literal))
+                .map_or(false, |ord| ord != Ordering::Greater),
+            other => return Err(GraphError::New(format!("unknown operator {other:?}"))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Properties;
+
+    fn node_with(age: i64, active: bool) -> Node {
+        let mut properties = Properties::new();
+        properties.insert("age".to_string(), Value::Integer(age));
+        properties.insert("active".to_string(), Value::Boolean(active));
+        Node::new("n1", "person", properties)
+    }
+
+    #[test]
+    fn and_expression_matches_only_when_both_sides_hold() {
+        let matching = node_with(25, true);
+        let too_young = node_with(19, true);
+        let inactive = node_with(25, false);
+
+        assert!(eval_filter("age > 21 && active == true", &matching).unwrap());
+        assert!(!eval_filter("age > 21 && active == true", &too_young).unwrap());
+        assert!(!eval_filter("age > 21 && active == true", &inactive).unwrap());
+    }
+
+    #[test]
+    fn or_expression_matches_when_either_side_holds() {
+        let node = node_with(19, true);
+        assert!(eval_filter("age > 21 || active == true", &node).unwrap());
+        assert!(!eval_filter("age > 21 || active == false", &node).unwrap());
+    }
+
+    #[test]
+    fn a_missing_key_fails_equality_and_satisfies_inequality() {
+        let node = node_with(25, true);
+        assert!(!eval_filter("city == \"nyc\"", &node).unwrap());
+        assert!(eval_filter("city != \"nyc\"", &node).unwrap());
+    }
+
+    #[test]
+    fn malformed_expressions_return_an_error_rather_than_panicking() {
+        let node = node_with(25, true);
+        assert!(eval_filter("age >", &node).is_err());
+        assert!(eval_filter("age ~ 1", &node).is_err());
+    }
+}
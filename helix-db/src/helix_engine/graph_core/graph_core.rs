@@ -0,0 +1,384 @@
+use super::cache::{CacheConfig, QueryCache};
+use super::traversal::TraversalBuilder;
+use crate::helix_engine::storage_core::{CreateMode, HelixGraphStorage, StorageMethods};
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{Edge, Node, Properties, Response};
+use rocksdb::WriteBatch;
+use serde::ser::SerializeSeq;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io::Write;
+
+/// A staged set of writes passed to a closure run through
+/// [`HelixGraphEngine::with_batch`]. Writes made through `create_node`/
+/// `create_edge` here aren't visible to reads until the closure returns
+/// `Ok` and the batch commits — if it returns `Err`, nothing staged is
+/// written at all.
+pub struct GraphBatch<'a> {
+    storage: &'a HelixGraphStorage,
+    batch: WriteBatch,
+}
+
+impl<'a> GraphBatch<'a> {
+    pub fn create_node(&mut self, label: &str, properties: Properties) -> Result<Node, GraphError> {
+        self.storage.stage_create_node(&mut self.batch, label, properties)
+    }
+
+    pub fn create_edge(
+        &mut self,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        self.storage
+            .stage_create_edge(&mut self.batch, label, from_id, to_id, properties)
+    }
+}
+
+/// Top-level handle to a running Helix instance: owns the storage layer and
+/// is the entry point handlers reach for when executing a query.
+pub struct HelixGraphEngine {
+    pub storage: HelixGraphStorage,
+    cache: QueryCache,
+}
+
+impl HelixGraphEngine {
+    pub fn new(path: &str) -> Result<Self, GraphError> {
+        Self::new_with_cache_config(path, CacheConfig::default())
+    }
+
+    pub fn new_with_cache_config(path: &str, cache_config: CacheConfig) -> Result<Self, GraphError> {
+        let storage = HelixGraphStorage::new(path)?;
+        Ok(HelixGraphEngine {
+            storage,
+            cache: QueryCache::new(cache_config),
+        })
+    }
+
+    /// Runs `query` through the result cache: a cache hit returns the stored
+    /// JSON body as-is, a miss calls `compute`, serializes its result, and
+    /// stores it under `query` for subsequent calls. The cache key is `query`
+    /// with leading and trailing whitespace removed and each inner run of
+    /// whitespace collapsed to one space. Any write made through
+    /// [`create_node`](Self::create_node), [`create_edge`](Self::create_edge),
+    /// [`drop_node`](Self::drop_node), or [`drop_edge`](Self::drop_edge)
+    /// invalidates every entry.
+    pub fn result_to_response<T, F>(&self, query: &str, compute: F) -> Result<Response, GraphError>
+    where
+        T: Serialize + Debug,
+        F: FnOnce() -> Result<T, GraphError>,
+    {
+        let key = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some(body) = self.cache.get(&key) {
+            let mut response = Response::ok(body);
+            response
+                .headers
+                .insert("Content-Type".to_string(), "application/json".to_string());
+            return Ok(response);
+        }
+
+        let data = compute()?;
+        let body = serde_json::to_vec(&data).map_err(|e| GraphError::StorageError(e.to_string()))?;
+        self.cache.put(key, body.clone());
+        let mut response = Response::ok(body);
+        response
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        Ok(response)
+    }
+
+    /// Thin pass-through to [`StorageMethods::get_node`], so a handler can
+    /// go through the engine's stable API instead of reaching into
+    /// `self.storage` directly and importing [`StorageMethods`] itself. A
+    /// read, so unlike the `create_*`/`drop_*` methods below it doesn't
+    /// touch the result cache.
+    pub fn get_node(&self, id: &str) -> Result<Node, GraphError> {
+        self.storage.get_node(id)
+    }
+
+    /// Mirrors [`HelixGraphEngine::get_node`] for edges.
+    pub fn get_edge(&self, id: &str) -> Result<Edge, GraphError> {
+        self.storage.get_edge(id)
+    }
+
+    pub fn create_node(&self, label: &str, properties: Properties) -> Result<Node, GraphError> {
+        let node = self.storage.create_node(label, properties)?;
+        self.cache.bump_generation();
+        Ok(node)
+    }
+
+    pub fn create_edge(
+        &self,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        let edge = self.storage.create_edge(label, from_id, to_id, properties)?;
+        self.cache.bump_generation();
+        Ok(edge)
+    }
+
+    /// Creates or upserts a node at a caller-chosen id. See
+    /// [`StorageMethods::create_node_with_id`] for how `mode` resolves an
+    /// id collision.
+    pub fn create_node_with_id(
+        &self,
+        id: &str,
+        label: &str,
+        properties: Properties,
+        mode: CreateMode,
+    ) -> Result<Node, GraphError> {
+        let node = self.storage.create_node_with_id(id, label, properties, mode)?;
+        self.cache.bump_generation();
+        Ok(node)
+    }
+
+    /// Bulk variant of [`create_edge`](Self::create_edge): see
+    /// [`StorageMethods::create_edges`] for the batching behavior.
+    pub fn create_edges(
+        &self,
+        edges: Vec<(String, String, String, Properties)>,
+    ) -> Result<Vec<Result<Edge, GraphError>>, GraphError> {
+        let results = self.storage.create_edges(edges)?;
+        self.cache.bump_generation();
+        Ok(results)
+    }
+
+    pub fn drop_node(&self, id: &str) -> Result<(), GraphError> {
+        self.storage.drop_node(id)?;
+        self.cache.bump_generation();
+        Ok(())
+    }
+
+    pub fn drop_edge(&self, id: &str) -> Result<Option<Edge>, GraphError> {
+        let edge = self.storage.drop_edge(id)?;
+        self.cache.bump_generation();
+        Ok(edge)
+    }
+
+    /// Isolates `node_id` by removing every edge touching it, leaving the
+    /// node itself in place. See [`StorageMethods::drop_node_edges`].
+    pub fn drop_node_edges(&self, node_id: &str) -> Result<usize, GraphError> {
+        let removed = self.storage.drop_node_edges(node_id)?;
+        self.cache.bump_generation();
+        Ok(removed)
+    }
+
+    /// Streams `traversal`'s current elements to `w` as a JSON array, one
+    /// element at a time, instead of collecting them into a `String` (or
+    /// `Vec<Node>`) first and serializing that in one shot — on a large
+    /// result this avoids holding two full copies of the body in memory at
+    /// once. Pair with [`crate::helix_gateway::worker::write_chunked_response`]
+    /// to stream the HTTP response too.
+    pub fn write_results_json<W: Write>(&self, traversal: &TraversalBuilder<'_>, w: &mut W) -> Result<(), GraphError> {
+        let mut serializer = serde_json::Serializer::new(w);
+        let mut seq = serializer
+            .serialize_seq(Some(traversal.current.len()))
+            .map_err(|e| GraphError::StorageError(e.to_string()))?;
+        for value in &traversal.current {
+            seq.serialize_element(value)
+                .map_err(|e| GraphError::StorageError(e.to_string()))?;
+        }
+        seq.end().map_err(|e| GraphError::StorageError(e.to_string()))
+    }
+
+    /// Serializes `traversal`'s current elements to a JSON array, compact
+    /// when `pretty` is `false` or indented when `true`, propagating a
+    /// serialization failure as [`GraphError::StorageError`] rather than
+    /// unwrapping it. For a large result that shouldn't be buffered twice,
+    /// prefer [`HelixGraphEngine::write_results_json`] instead.
+    pub fn serialize_result(&self, traversal: &TraversalBuilder<'_>, pretty: bool) -> Result<Vec<u8>, GraphError> {
+        if pretty {
+            serde_json::to_vec_pretty(&traversal.current)
+        } else {
+            serde_json::to_vec(&traversal.current)
+        }
+        .map_err(|e| GraphError::StorageError(e.to_string()))
+    }
+
+    /// Serializes a [`TraversalBuilder::collect_map`]/
+    /// [`TraversalBuilder::collect_edge_map`] result to a JSON object (id to
+    /// node/edge) rather than the JSON array [`HelixGraphEngine::serialize_result`]
+    /// produces, matching the shape of a client-side lookup table.
+    pub fn serialize_map_result<T: serde::Serialize>(
+        &self,
+        map: &HashMap<String, T>,
+        pretty: bool,
+    ) -> Result<Vec<u8>, GraphError> {
+        if pretty {
+            serde_json::to_vec_pretty(map)
+        } else {
+            serde_json::to_vec(map)
+        }
+        .map_err(|e| GraphError::StorageError(e.to_string()))
+    }
+
+    /// Runs `f` against a [`GraphBatch`] and commits everything it staged in
+    /// one atomic RocksDB write. If `f` returns `Err`, the batch is dropped
+    /// without writing anything, so a handler building up several
+    /// create_node/create_edge calls can't leave the graph half-written if
+    /// a later step fails.
+    pub fn with_batch<F>(&self, f: F) -> Result<(), GraphError>
+    where
+        F: FnOnce(&mut GraphBatch) -> Result<(), GraphError>,
+    {
+        let mut graph_batch = GraphBatch {
+            storage: &self.storage,
+            batch: WriteBatch::default(),
+        };
+        f(&mut graph_batch)?;
+        self.storage.db.write(graph_batch.batch)?;
+        self.cache.bump_generation();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn temp_engine(cache_config: CacheConfig) -> HelixGraphEngine {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphEngine::new_with_cache_config(dir.to_str().unwrap(), cache_config).unwrap()
+    }
+
+    #[test]
+    fn repeated_query_hits_cache_and_write_invalidates_it() {
+        use std::cell::Cell;
+
+        let engine = temp_engine(CacheConfig {
+            enabled: true,
+            ttl: Duration::from_secs(60),
+            max_entries: 16,
+        });
+
+        let calls = Cell::new(0);
+        let run = |engine: &HelixGraphEngine| {
+            engine
+                .result_to_response("MATCH (n)", || -> Result<Vec<String>, GraphError> {
+                    calls.set(calls.get() + 1);
+                    Ok(vec!["a".to_string()])
+                })
+                .unwrap()
+        };
+
+        run(&engine);
+        assert_eq!(calls.get(), 1);
+
+        // Same query again: should hit the cache, not recompute.
+        run(&engine);
+        assert_eq!(calls.get(), 1);
+
+        engine.create_node("person", HashMap::new()).unwrap();
+
+        run(&engine);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn with_batch_commits_all_writes_atomically() {
+        let engine = temp_engine(CacheConfig::default());
+
+        engine
+            .with_batch(|batch| {
+                let a = batch.create_node("person", HashMap::new())?;
+                let b = batch.create_node("person", HashMap::new())?;
+                batch.create_edge("knows", &a.id, &b.id, HashMap::new())?;
+                batch.create_edge("knows", &b.id, &a.id, HashMap::new())?;
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(engine.storage.get_all_nodes().unwrap().len(), 2);
+        assert_eq!(engine.storage.get_all_edges().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn with_batch_rolls_back_every_staged_write_on_failure() {
+        let engine = temp_engine(CacheConfig::default());
+
+        let result = engine.with_batch(|batch| {
+            let a = batch.create_node("person", HashMap::new())?;
+            let b = batch.create_node("person", HashMap::new())?;
+            batch.create_edge("knows", &a.id, &b.id, HashMap::new())?;
+            batch.create_edge("knows", &b.id, &a.id, HashMap::new())?;
+            Err(GraphError::New("simulated failure".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(engine.storage.get_all_nodes().unwrap().len(), 0);
+        assert_eq!(engine.storage.get_all_edges().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn write_results_json_streams_a_large_result_and_parses_back() {
+        let engine = temp_engine(CacheConfig::default());
+        for _ in 0..10_000 {
+            engine.create_node("item", HashMap::new()).unwrap();
+        }
+
+        let traversal = TraversalBuilder::v_all(&engine.storage).unwrap();
+        let mut sink = Vec::new();
+        engine.write_results_json(&traversal, &mut sink).unwrap();
+
+        let parsed: Vec<serde_json::Value> = serde_json::from_slice(&sink).unwrap();
+        assert_eq!(parsed.len(), 10_000);
+    }
+
+    #[test]
+    fn serialize_result_pretty_has_newlines_and_compact_does_not() {
+        let engine = temp_engine(CacheConfig::default());
+        engine.create_node("item", HashMap::new()).unwrap();
+        engine.create_node("item", HashMap::new()).unwrap();
+
+        let traversal = TraversalBuilder::v_all(&engine.storage).unwrap();
+        let compact = engine.serialize_result(&traversal, false).unwrap();
+        let pretty = engine.serialize_result(&traversal, true).unwrap();
+
+        assert!(!compact.contains(&b'\n'));
+        assert!(pretty.contains(&b'\n'));
+
+        let compact_parsed: Vec<serde_json::Value> = serde_json::from_slice(&compact).unwrap();
+        let pretty_parsed: Vec<serde_json::Value> = serde_json::from_slice(&pretty).unwrap();
+        assert_eq!(compact_parsed, pretty_parsed);
+    }
+
+    #[test]
+    fn serialize_map_result_serializes_collect_map_as_a_json_object_keyed_by_id() {
+        let engine = temp_engine(CacheConfig::default());
+        let a = engine.create_node("item", HashMap::new()).unwrap();
+        let b = engine.create_node("item", HashMap::new()).unwrap();
+
+        let map = TraversalBuilder::v_all(&engine.storage).unwrap().collect_map();
+        assert_eq!(map.len(), 2);
+
+        let bytes = engine.serialize_map_result(&map, false).unwrap();
+        let parsed: HashMap<String, serde_json::Value> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.contains_key(&a.id));
+        assert!(parsed.contains_key(&b.id));
+    }
+
+    #[test]
+    fn a_handler_using_only_engine_methods_can_create_and_fetch_a_node() {
+        let engine = temp_engine(CacheConfig::default());
+
+        let created = engine.create_node("person", HashMap::new()).unwrap();
+        let fetched = engine.get_node(&created.id).unwrap();
+        assert_eq!(fetched.id, created.id);
+
+        let edge = engine
+            .create_edge("knows", &created.id, &created.id, HashMap::new())
+            .unwrap();
+        let fetched_edge = engine.get_edge(&edge.id).unwrap();
+        assert_eq!(fetched_edge.id, edge.id);
+
+        engine.drop_node(&created.id).unwrap();
+        assert!(engine.get_node(&created.id).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod cache;
+pub mod filter_expr;
+pub mod graph_core;
+pub mod pathfinding;
+pub mod spill;
+pub mod traversal;
+
+pub use cache::{CacheConfig, QueryCache};
+pub use filter_expr::eval_filter;
+pub use graph_core::{GraphBatch, HelixGraphEngine};
+pub use pathfinding::{extract_subgraph, shortest_path, weighted_shortest_path};
+pub use spill::SpillStore;
+pub use traversal::{TraversalBuilder, TraversalValue};
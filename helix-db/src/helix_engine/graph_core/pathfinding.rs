@@ -0,0 +1,307 @@
+use crate::helix_engine::storage_core::HelixGraphStorage;
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{Edge, Node, Value};
+use rocksdb::IteratorMode;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+fn out_edges(storage: &HelixGraphStorage, node_id: &str, edge_label: &str) -> Result<Vec<Edge>, GraphError> {
+    let prefix = format!("o:{node_id}:");
+    let iter = storage.db.iterator_cf_opt(
+        storage.cf_indices(),
+        storage.read_opts(),
+        IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+    );
+    let mut edges = Vec::new();
+    for item in iter {
+        let (key, bytes) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+        let edge: Edge = storage.deserialize_edge(&bytes)?;
+        if edge.label == edge_label {
+            edges.push(edge);
+        }
+    }
+    Ok(edges)
+}
+
+/// Fewest-hops path from `from` to `to` following edges labelled
+/// `edge_label`, via BFS. Returns `None` if no path exists.
+pub fn shortest_path(
+    storage: &HelixGraphStorage,
+    from: &str,
+    to: &str,
+    edge_label: &str,
+) -> Result<Option<Vec<String>>, GraphError> {
+    if from == to {
+        return Ok(Some(vec![from.to_string()]));
+    }
+
+    let mut visited = HashSet::new();
+    visited.insert(from.to_string());
+    let mut queue = VecDeque::new();
+    queue.push_back(from.to_string());
+    let mut predecessor: HashMap<String, String> = HashMap::new();
+
+    while let Some(current) = queue.pop_front() {
+        for edge in out_edges(storage, &current, edge_label)? {
+            if visited.insert(edge.to_node.clone()) {
+                predecessor.insert(edge.to_node.clone(), current.clone());
+                if edge.to_node == to {
+                    return Ok(Some(reconstruct_path(&predecessor, from, to)));
+                }
+                queue.push_back(edge.to_node);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn reconstruct_path(predecessor: &HashMap<String, String>, from: &str, to: &str) -> Vec<String> {
+    let mut path = vec![to.to_string()];
+    let mut current = to.to_string();
+    while current != from {
+        current = predecessor[&current].clone();
+        path.push(current.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[derive(PartialEq)]
+struct HeapEntry {
+    cost: f64,
+    node: String,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) pops the lowest cost first.
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn edge_weight(edge: &Edge, weight_prop: &str) -> f64 {
+    match edge.properties.get(weight_prop) {
+        Some(Value::Float(f)) => *f,
+        Some(Value::Integer(i)) => *i as f64,
+        _ => 1.0,
+    }
+}
+
+/// Least-cost path from `from` to `to` following edges labelled
+/// `edge_label`, using each edge's `weight_prop` (defaulting to `1.0` when
+/// absent or not numeric) as Dijkstra edge cost. Search is abandoned once
+/// the frontier's minimum cost exceeds `max_cost`.
+pub fn weighted_shortest_path(
+    storage: &HelixGraphStorage,
+    from: &str,
+    to: &str,
+    edge_label: &str,
+    weight_prop: &str,
+    max_cost: f64,
+) -> Result<Option<(Vec<String>, f64)>, GraphError> {
+    let mut dist: HashMap<String, f64> = HashMap::new();
+    let mut predecessor: HashMap<String, String> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    dist.insert(from.to_string(), 0.0);
+    heap.push(HeapEntry {
+        cost: 0.0,
+        node: from.to_string(),
+    });
+
+    while let Some(HeapEntry { cost, node }) = heap.pop() {
+        if cost > max_cost {
+            break;
+        }
+        if node == to {
+            return Ok(Some((reconstruct_path(&predecessor, from, to), cost)));
+        }
+        if cost > *dist.get(&node).unwrap_or(&f64::INFINITY) {
+            continue;
+        }
+
+        for edge in out_edges(storage, &node, edge_label)? {
+            let next_cost = cost + edge_weight(&edge, weight_prop);
+            if next_cost > max_cost {
+                continue;
+            }
+            if next_cost < *dist.get(&edge.to_node).unwrap_or(&f64::INFINITY) {
+                dist.insert(edge.to_node.clone(), next_cost);
+                predecessor.insert(edge.to_node.clone(), node.clone());
+                heap.push(HeapEntry {
+                    cost: next_cost,
+                    node: edge.to_node,
+                });
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn adjacent_edges(
+    storage: &HelixGraphStorage,
+    node_id: &str,
+    edge_label: Option<&str>,
+) -> Result<Vec<Edge>, GraphError> {
+    let mut edges = Vec::new();
+    for direction in ['o', 'i'] {
+        let prefix = format!("{direction}:{node_id}:");
+        let iter = storage.db.iterator_cf_opt(
+            storage.cf_indices(),
+            storage.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        for item in iter {
+            let (key, bytes) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let edge: Edge = storage.deserialize_edge(&bytes)?;
+            if edge_label.map_or(true, |label| edge.label == label) {
+                edges.push(edge);
+            }
+        }
+    }
+    Ok(edges)
+}
+
+/// Caps how large a BFS neighborhood [`extract_subgraph`] will collect, so a
+/// seed that turns out to sit next to a hub node doesn't pull in a
+/// near-unbounded number of nodes before `depth` is exhausted.
+const SUBGRAPH_MAX_SIZE: usize = 10_000;
+
+/// Returns the BFS k-hop neighborhood around `seed_id`: every node reachable
+/// within `depth` hops, following edges in either direction and matching
+/// `edge_label` when given (every label otherwise), plus every edge the BFS
+/// crossed to reach them. Expansion stops early, even if `depth` hasn't been
+/// exhausted, once the visited set reaches [`SUBGRAPH_MAX_SIZE`] nodes — any
+/// edge left dangling to a node the guard cut off is dropped rather than
+/// included with a missing endpoint.
+pub fn extract_subgraph(
+    storage: &HelixGraphStorage,
+    seed_id: &str,
+    depth: usize,
+    edge_label: Option<&str>,
+) -> Result<(Vec<Node>, Vec<Edge>), GraphError> {
+    let mut visited_ids = HashSet::new();
+    visited_ids.insert(seed_id.to_string());
+    let mut frontier = vec![seed_id.to_string()];
+    let mut edges: HashMap<String, Edge> = HashMap::new();
+
+    for _ in 0..depth {
+        if visited_ids.len() >= SUBGRAPH_MAX_SIZE {
+            break;
+        }
+        let mut next_frontier = Vec::new();
+        for node_id in &frontier {
+            for edge in adjacent_edges(storage, node_id, edge_label)? {
+                let other = if edge.from_node == *node_id {
+                    edge.to_node.clone()
+                } else {
+                    edge.from_node.clone()
+                };
+                edges.insert(edge.id.clone(), edge);
+                if visited_ids.len() < SUBGRAPH_MAX_SIZE && visited_ids.insert(other.clone()) {
+                    next_frontier.push(other);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    edges.retain(|_, edge| visited_ids.contains(&edge.from_node) && visited_ids.contains(&edge.to_node));
+
+    let mut nodes = Vec::with_capacity(visited_ids.len());
+    for id in &visited_ids {
+        nodes.push(storage.get_node(id)?);
+    }
+
+    Ok((nodes, edges.into_values().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helix_engine::storage_core::StorageMethods;
+    use std::collections::HashMap as StdHashMap;
+
+    fn temp_storage() -> HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn weighted_path_differs_from_fewest_hops_path() {
+        let storage = temp_storage();
+        let a = storage.create_node("loc", StdHashMap::new()).unwrap();
+        let b = storage.create_node("loc", StdHashMap::new()).unwrap();
+        let c = storage.create_node("loc", StdHashMap::new()).unwrap();
+        let d = storage.create_node("loc", StdHashMap::new()).unwrap();
+
+        // Direct a->d costs 10, the two-hop a->b->d costs 1+1 = 2.
+        let mut direct = StdHashMap::new();
+        direct.insert("cost".to_string(), Value::Float(10.0));
+        storage.create_edge("road", &a.id, &d.id, direct).unwrap();
+
+        let mut cheap1 = StdHashMap::new();
+        cheap1.insert("cost".to_string(), Value::Float(1.0));
+        storage.create_edge("road", &a.id, &b.id, cheap1).unwrap();
+        let mut cheap2 = StdHashMap::new();
+        cheap2.insert("cost".to_string(), Value::Float(1.0));
+        storage.create_edge("road", &b.id, &d.id, cheap2).unwrap();
+
+        // Unrelated detour so the BFS hop-count path isn't the cheap one.
+        storage.create_edge("road", &a.id, &c.id, StdHashMap::new()).unwrap();
+
+        let hops = shortest_path(&storage, &a.id, &d.id, "road").unwrap().unwrap();
+        assert_eq!(hops, vec![a.id.clone(), d.id.clone()]);
+
+        let (path, cost) = weighted_shortest_path(&storage, &a.id, &d.id, "road", "cost", 100.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, vec![a.id.clone(), b.id.clone(), d.id.clone()]);
+        assert_eq!(cost, 2.0);
+    }
+
+    #[test]
+    fn subgraph_grows_with_depth_and_only_includes_internal_edges() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", StdHashMap::new()).unwrap();
+        let b = storage.create_node("person", StdHashMap::new()).unwrap();
+        let c = storage.create_node("person", StdHashMap::new()).unwrap();
+        let d = storage.create_node("person", StdHashMap::new()).unwrap();
+
+        storage.create_edge("knows", &a.id, &b.id, StdHashMap::new()).unwrap();
+        storage.create_edge("knows", &b.id, &c.id, StdHashMap::new()).unwrap();
+        storage.create_edge("knows", &c.id, &d.id, StdHashMap::new()).unwrap();
+
+        let (nodes, edges) = extract_subgraph(&storage, &a.id, 1, Some("knows")).unwrap();
+        let mut node_ids: Vec<String> = nodes.into_iter().map(|n| n.id).collect();
+        node_ids.sort();
+        let mut expected = vec![a.id.clone(), b.id.clone()];
+        expected.sort();
+        assert_eq!(node_ids, expected);
+        assert_eq!(edges.len(), 1);
+
+        let (nodes, edges) = extract_subgraph(&storage, &a.id, 2, Some("knows")).unwrap();
+        let mut node_ids: Vec<String> = nodes.into_iter().map(|n| n.id).collect();
+        node_ids.sort();
+        let mut expected = vec![a.id.clone(), b.id.clone(), c.id.clone()];
+        expected.sort();
+        assert_eq!(node_ids, expected);
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| e.from_node != d.id && e.to_node != d.id));
+    }
+}
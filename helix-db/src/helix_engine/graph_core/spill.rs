@@ -0,0 +1,65 @@
+use crate::helix_engine::types::GraphError;
+use rocksdb::{IteratorMode, DB};
+
+/// A temporary, disk-backed overflow buffer for [`super::TraversalBuilder`]
+/// source steps whose result set is too large to hold entirely in memory at
+/// once.
+///
+/// This only bounds peak memory during the *scan* that fills `current` — a
+/// genuine `current_step` that streams lazily through every later step (the
+/// "substantial redesign" called out in the request this implements) is out
+/// of scope here. Once drained, the caller still ends up with the full
+/// result in memory, same as an unspilled scan; what spilling buys is never
+/// holding more than `threshold` elements resident *during* the scan, so a
+/// scan that would otherwise peak at holding the whole graph in RAM instead
+/// peaks at `threshold`.
+pub struct SpillStore {
+    db: Option<DB>,
+    path: std::path::PathBuf,
+    next_key: u64,
+}
+
+impl SpillStore {
+    /// Opens a fresh temporary RocksDB database to spill into. Each store is
+    /// only ever used by one scan and removed afterwards by
+    /// [`Drop`].
+    pub fn new() -> Result<Self, GraphError> {
+        let path = std::env::temp_dir().join(format!("helix-spill-{}", uuid::Uuid::new_v4()));
+        let db = DB::open_default(&path).map_err(|e| GraphError::StorageError(e.to_string()))?;
+        Ok(SpillStore {
+            db: Some(db),
+            path,
+            next_key: 0,
+        })
+    }
+
+    /// Appends one already-serialized element to the spill store.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<(), GraphError> {
+        self.db
+            .as_ref()
+            .expect("SpillStore used after close")
+            .put(self.next_key.to_be_bytes(), bytes)
+            .map_err(|e| GraphError::StorageError(e.to_string()))?;
+        self.next_key += 1;
+        Ok(())
+    }
+
+    /// Reads every spilled element back out, in the order they were pushed,
+    /// decoding each with `decode`.
+    pub fn drain_into<T>(&self, decode: impl Fn(&[u8]) -> Result<T, GraphError>) -> Result<Vec<T>, GraphError> {
+        let db = self.db.as_ref().expect("SpillStore used after close");
+        let mut out = Vec::with_capacity(self.next_key as usize);
+        for item in db.iterator(IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            out.push(decode(&value)?);
+        }
+        Ok(out)
+    }
+}
+
+impl Drop for SpillStore {
+    fn drop(&mut self) {
+        self.db.take();
+        let _ = DB::destroy(&rocksdb::Options::default(), &self.path);
+    }
+}
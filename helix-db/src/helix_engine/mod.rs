@@ -0,0 +1,5 @@
+pub mod graph_core;
+pub mod storage_core;
+pub mod types;
+
+pub use types::GraphError;
@@ -0,0 +1,119 @@
+use super::storage_core::{HelixGraphStorage, CF_EDGES, CF_INDICES, CF_NODES};
+use crate::helix_engine::types::GraphError;
+
+/// Live vs on-disk size for one column family, from RocksDB's own
+/// `rocksdb.estimate-live-data-size`/`rocksdb.total-sst-files-size`
+/// properties. `total_sst_size - live_data_size` is roughly how many bytes
+/// a compaction would reclaim.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnFamilyDiskUsage {
+    pub live_data_size: u64,
+    pub total_sst_size: u64,
+}
+
+/// A [`HelixGraphStorage::size_on_disk`] snapshot, broken down per column
+/// family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub nodes: ColumnFamilyDiskUsage,
+    pub edges: ColumnFamilyDiskUsage,
+    pub indices: ColumnFamilyDiskUsage,
+}
+
+impl DiskUsage {
+    pub fn total_live_data_size(&self) -> u64 {
+        self.nodes.live_data_size + self.edges.live_data_size + self.indices.live_data_size
+    }
+
+    pub fn total_sst_size(&self) -> u64 {
+        self.nodes.total_sst_size + self.edges.total_sst_size + self.indices.total_sst_size
+    }
+}
+
+impl HelixGraphStorage {
+    /// Reports live data size and total SST file size for each column
+    /// family, cheaply (RocksDB property reads, no scan). A column family
+    /// whose `total_sst_size` is much larger than its `live_data_size` has
+    /// that much space pending reclamation by compaction — see
+    /// [`HelixGraphStorage::compact`].
+    pub fn size_on_disk(&self) -> Result<DiskUsage, GraphError> {
+        Ok(DiskUsage {
+            nodes: self.column_family_disk_usage(self.cf_nodes())?,
+            edges: self.column_family_disk_usage(self.cf_edges())?,
+            indices: self.column_family_disk_usage(self.cf_indices())?,
+        })
+    }
+
+    fn column_family_disk_usage(&self, cf: &rocksdb::ColumnFamily) -> Result<ColumnFamilyDiskUsage, GraphError> {
+        let live_data_size = self
+            .db
+            .property_int_value_cf(cf, "rocksdb.estimate-live-data-size")
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+            .unwrap_or(0);
+        let total_sst_size = self
+            .db
+            .property_int_value_cf(cf, "rocksdb.total-sst-files-size")
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+            .unwrap_or(0);
+        Ok(ColumnFamilyDiskUsage {
+            live_data_size,
+            total_sst_size,
+        })
+    }
+
+    /// Runs a full manual compaction over every column family, letting
+    /// RocksDB reclaim space held by overwritten/deleted records. This is a
+    /// synchronous, potentially expensive operation — callers should only
+    /// invoke it off the request path (e.g. from an operator tool or a
+    /// scheduled maintenance job).
+    pub fn compact(&self) {
+        for cf in [CF_NODES, CF_EDGES, CF_INDICES] {
+            let handle = self.db.cf_handle(cf).expect("column family must exist");
+            self.db.compact_range_cf(handle, None::<&[u8]>, None::<&[u8]>);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helix_engine::storage_core::StorageMethods;
+    use std::collections::HashMap;
+
+    fn temp_storage() -> HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn compact_does_not_widen_the_gap_between_total_and_live_size_left_by_deleted_records() {
+        let storage = temp_storage();
+        let mut ids = Vec::new();
+        for _ in 0..2000 {
+            let mut props = HashMap::new();
+            props.insert("payload".to_string(), crate::protocol::Value::String("x".repeat(256)));
+            ids.push(storage.create_node("item", props).unwrap().id);
+        }
+        for id in &ids {
+            storage.drop_node(id).unwrap();
+        }
+        storage.db.flush().unwrap();
+
+        let before = storage.size_on_disk().unwrap();
+        let gap_before = before.total_sst_size().saturating_sub(before.total_live_data_size());
+        assert!(
+            before.total_sst_size() >= before.total_live_data_size(),
+            "total SST size should never be smaller than the live-data estimate"
+        );
+
+        storage.compact();
+
+        let after = storage.size_on_disk().unwrap();
+        let gap_after = after.total_sst_size().saturating_sub(after.total_live_data_size());
+
+        assert!(
+            gap_after <= gap_before,
+            "expected compact() to not increase the pending-reclaim gap: before={gap_before}, after={gap_after}"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use super::storage_core::HelixGraphStorage;
+use crate::helix_engine::types::GraphError;
+use crate::protocol::Edge;
+use rocksdb::IteratorMode;
+
+/// Counts of dangling entries found by [`HelixGraphStorage::verify_integrity`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IntegrityReport {
+    pub dangling_out_adjacency: usize,
+    pub dangling_in_adjacency: usize,
+    pub dangling_index_entries: usize,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.dangling_out_adjacency == 0
+            && self.dangling_in_adjacency == 0
+            && self.dangling_index_entries == 0
+    }
+}
+
+impl HelixGraphStorage {
+    /// Cross-checks every `o:`/`i:` adjacency entry against a live `e:`
+    /// record and a live destination/source node, and every `ei:` edge
+    /// property-index entry against a live edge record. All three key kinds
+    /// live in `CF_INDICES`, so a single scan over it covers them. Other
+    /// index keys (`nl:`/`el:` label-index entries, `deg:` counters) are not
+    /// checked and are never counted as dangling. Returns counts
+    /// only; use [`HelixGraphStorage::repair`] to actually delete dangling
+    /// entries.
+    pub fn verify_integrity(&self) -> Result<IntegrityReport, GraphError> {
+        let mut report = IntegrityReport::default();
+
+        for item in self.db.iterator_cf_opt(self.cf_indices(), self.read_opts(), IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if key.starts_with(b"o:") {
+                if !self.adjacency_entry_is_live(&value)? {
+                    report.dangling_out_adjacency += 1;
+                }
+            } else if key.starts_with(b"i:") {
+                if !self.adjacency_entry_is_live(&value)? {
+                    report.dangling_in_adjacency += 1;
+                }
+            } else if key.starts_with(b"ei:") && !self.edge_index_entry_is_live(&key)? {
+                report.dangling_index_entries += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Deletes every dangling entry [`HelixGraphStorage::verify_integrity`]
+    /// would report. Returns the report describing what was removed.
+    pub fn repair(&self) -> Result<IntegrityReport, GraphError> {
+        let mut report = IntegrityReport::default();
+        let mut to_delete = Vec::new();
+
+        for item in self.db.iterator_cf_opt(self.cf_indices(), self.read_opts(), IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if key.starts_with(b"o:") {
+                if !self.adjacency_entry_is_live(&value)? {
+                    to_delete.push(key.to_vec());
+                    report.dangling_out_adjacency += 1;
+                }
+            } else if key.starts_with(b"i:") {
+                if !self.adjacency_entry_is_live(&value)? {
+                    to_delete.push(key.to_vec());
+                    report.dangling_in_adjacency += 1;
+                }
+            } else if key.starts_with(b"ei:") && !self.edge_index_entry_is_live(&key)? {
+                to_delete.push(key.to_vec());
+                report.dangling_index_entries += 1;
+            }
+        }
+        for key in to_delete {
+            self.db.delete_cf(self.cf_indices(), key)?;
+        }
+
+        Ok(report)
+    }
+
+    fn adjacency_entry_is_live(&self, value: &[u8]) -> Result<bool, GraphError> {
+        let edge: Edge = self.deserialize_edge(value)?;
+        let edge_record_exists = self
+            .db
+            .get_cf_opt(self.cf_edges(), format!("e:{}", edge.id), &self.read_opts())?
+            .is_some();
+        let from_exists = self
+            .db
+            .get_cf_opt(self.cf_nodes(), format!("n:{}", edge.from_node), &self.read_opts())?
+            .is_some();
+        let to_exists = self
+            .db
+            .get_cf_opt(self.cf_nodes(), format!("n:{}", edge.to_node), &self.read_opts())?
+            .is_some();
+        Ok(edge_record_exists && from_exists && to_exists)
+    }
+
+    fn edge_index_entry_is_live(&self, key: &[u8]) -> Result<bool, GraphError> {
+        let key_str = String::from_utf8_lossy(key);
+        let edge_id = key_str.rsplit(':').next().unwrap_or_default();
+        Ok(self
+            .db
+            .get_cf_opt(self.cf_edges(), format!("e:{edge_id}"), &self.read_opts())?
+            .is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helix_engine::storage_core::StorageMethods;
+    use std::collections::HashMap;
+
+    fn temp_storage() -> HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn verify_integrity_reports_dangling_out_adjacency() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let edge = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+
+        assert!(storage.verify_integrity().unwrap().is_clean());
+
+        // Simulate the historical drop_node bug: delete the edge record but
+        // leave the out-adjacency entry behind.
+        storage.db.delete_cf(storage.cf_edges(), format!("e:{}", edge.id)).unwrap();
+
+        let report = storage.verify_integrity().unwrap();
+        assert_eq!(report.dangling_out_adjacency, 1);
+        assert!(!report.is_clean());
+
+        let repaired = storage.repair().unwrap();
+        assert_eq!(repaired.dangling_out_adjacency, 1);
+        assert!(storage.verify_integrity().unwrap().is_clean());
+    }
+
+    #[test]
+    fn node_and_edge_scans_never_encounter_index_keys() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+
+        for item in storage.db.iterator_cf_opt(storage.cf_nodes(), storage.read_opts(), IteratorMode::Start) {
+            let (key, _) = item.unwrap();
+            assert!(key.starts_with(b"n:"), "CF_NODES held a non-node key: {key:?}");
+        }
+        for item in storage.db.iterator_cf_opt(storage.cf_edges(), storage.read_opts(), IteratorMode::Start) {
+            let (key, _) = item.unwrap();
+            assert!(key.starts_with(b"e:"), "CF_EDGES held a non-edge key: {key:?}");
+        }
+    }
+}
@@ -0,0 +1,16 @@
+pub mod codec;
+pub mod disk_usage;
+pub mod import;
+pub mod integrity;
+pub mod patch;
+pub mod stats;
+pub mod storage_core;
+pub mod storage_methods;
+
+pub use codec::SerializationFormat;
+pub use disk_usage::{ColumnFamilyDiskUsage, DiskUsage};
+pub use import::ImportStats;
+pub use integrity::IntegrityReport;
+pub use stats::GraphStats;
+pub use storage_core::{HelixGraphStorage, StorageConfig, WalRecoveryMode, CF_EDGES, CF_INDICES, CF_NODES};
+pub use storage_methods::{CreateMode, StorageMethods};
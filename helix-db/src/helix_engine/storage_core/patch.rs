@@ -0,0 +1,101 @@
+use super::storage_core::HelixGraphStorage;
+use super::storage_methods::StorageMethods;
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{Node, Properties, Value};
+
+impl HelixGraphStorage {
+    /// Applies a JSON-merge-patch-style partial update to a node's
+    /// properties: each `patch` entry either sets a key to its value or, if
+    /// the value is [`Value::Empty`], removes that key. Keys not mentioned
+    /// in `patch` are left untouched. Returns the node with its properties
+    /// after the patch.
+    pub fn patch_node(&self, id: &str, patch: Properties) -> Result<Node, GraphError> {
+        self.reject_if_read_only()?;
+        let mut node = self.get_node(id)?;
+        for (key, value) in patch {
+            match value {
+                Value::Empty => {
+                    node.properties.remove(&key);
+                }
+                value => {
+                    node.properties.insert(key, value);
+                }
+            }
+        }
+        let bytes = self.serialize_node(&node)?;
+        self.db.put_cf(self.cf_nodes(), format!("n:{id}"), bytes)?;
+        Ok(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as StdHashMap;
+
+    fn temp_storage() -> HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn patch_node_sets_a_new_key_and_overwrites_an_existing_one() {
+        let storage = temp_storage();
+        let mut props = StdHashMap::new();
+        props.insert("name".to_string(), Value::String("Ada".to_string()));
+        let node = storage.create_node("person", props).unwrap();
+
+        let mut patch = StdHashMap::new();
+        patch.insert("name".to_string(), Value::String("Grace".to_string()));
+        patch.insert("age".to_string(), Value::Integer(30));
+        let patched = storage.patch_node(&node.id, patch).unwrap();
+
+        assert_eq!(patched.properties.get("name"), Some(&Value::String("Grace".to_string())));
+        assert_eq!(patched.properties.get("age"), Some(&Value::Integer(30)));
+
+        let reloaded = storage.get_node(&node.id).unwrap();
+        assert_eq!(reloaded.properties.get("name"), Some(&Value::String("Grace".to_string())));
+    }
+
+    #[test]
+    fn patch_node_removes_a_key_set_to_empty_and_leaves_others_untouched() {
+        let storage = temp_storage();
+        let mut props = StdHashMap::new();
+        props.insert("name".to_string(), Value::String("Ada".to_string()));
+        props.insert("bio".to_string(), Value::String("mathematician".to_string()));
+        let node = storage.create_node("person", props).unwrap();
+
+        let mut patch = StdHashMap::new();
+        patch.insert("bio".to_string(), Value::Empty);
+        let patched = storage.patch_node(&node.id, patch).unwrap();
+
+        assert!(!patched.properties.contains_key("bio"));
+        assert_eq!(patched.properties.get("name"), Some(&Value::String("Ada".to_string())));
+    }
+
+    #[test]
+    fn patch_node_errors_in_read_only_mode() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let node_id = {
+            let storage = HelixGraphStorage::new(dir.to_str().unwrap()).unwrap();
+            storage.create_node("person", StdHashMap::new()).unwrap().id
+        };
+
+        let storage = HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                read_only: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut patch = StdHashMap::new();
+        patch.insert("name".to_string(), Value::String("Ada".to_string()));
+        match storage.patch_node(&node_id, patch) {
+            Err(GraphError::StorageError(msg)) => assert_eq!(msg, "read-only"),
+            other => panic!("expected a read-only StorageError, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,860 @@
+use super::codec::{self, SerializationFormat};
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{Edge, Node, Properties, Value};
+use rocksdb::{ColumnFamilyDescriptor, DBRecoveryMode, Options, ReadOptions, DB};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+pub const CF_NODES: &str = "nodes";
+pub const CF_EDGES: &str = "edges";
+pub const CF_INDICES: &str = "indices";
+
+const FORMAT_META_KEY: &str = "meta:serialization_format";
+const PROPERTY_KEY_COUNTER: &str = "pk:__next_id__";
+
+fn property_key_forward_key(key: &str) -> String {
+    format!("pk:{key}")
+}
+
+fn property_key_reverse_key(id: u32) -> String {
+    format!("pki:{id}")
+}
+
+/// On-disk shape for a node record. Tagged so [`HelixGraphStorage::deserialize_node`]
+/// can tell which shape it's reading regardless of the store's current
+/// [`StorageConfig::intern_property_keys`] setting — e.g. right after that
+/// setting is flipped on an already-populated store, whose existing records
+/// are still `Plain`.
+#[derive(Debug, Serialize, Deserialize)]
+enum NodeRecord {
+    Plain(Node),
+    Interned {
+        id: String,
+        label: String,
+        properties: Vec<(u32, Value)>,
+    },
+}
+
+/// Mirrors [`NodeRecord`] for edges.
+#[derive(Debug, Serialize, Deserialize)]
+enum EdgeRecord {
+    Plain(Edge),
+    Interned {
+        id: String,
+        label: String,
+        from_node: String,
+        to_node: String,
+        properties: Vec<(u32, Value)>,
+    },
+}
+
+/// Tunables for how a [`HelixGraphStorage`] opens and reads its RocksDB
+/// handle.
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    /// Whether reads verify block checksums. Defaults to `true` — operators
+    /// who want the extra read throughput of skipping verification must opt
+    /// out explicitly.
+    pub verify_checksums: bool,
+    /// Caps how many rows an unbounded `v()`/`e()` traversal source step
+    /// reads before the caller has explicitly asked for everything (via
+    /// `v_all()`/`e_all()`) or an explicit count (`v_limit()`/`e_limit()`).
+    /// Defaults to `Some(1000)` so forgetting a limit on a large graph
+    /// doesn't serialize the whole thing; set to `None` to make `v()`/`e()`
+    /// unbounded again.
+    pub default_scan_limit: Option<usize>,
+    /// Whether `create_node`/`create_edge` stamp a `created_at` property
+    /// (epoch milliseconds) onto properties that don't already have one.
+    /// Defaults to `true`; set to `false` if the caller manages their own
+    /// creation timestamps and doesn't want the extra property.
+    pub auto_created_at: bool,
+    /// How tolerant opening the store is of a write-ahead log left
+    /// incomplete by a crash mid-write. Defaults to `PointInTime` — see
+    /// [`WalRecoveryMode`] for the tradeoffs of each setting.
+    pub wal_recovery_mode: WalRecoveryMode,
+    /// Serialized node/edge records larger than this many bytes are
+    /// zstd-compressed before being written; smaller records are stored
+    /// as-is to avoid paying compression overhead on every small write.
+    /// Defaults to 4 KiB. See [`HelixGraphStorage::serialize`].
+    pub compression_threshold: usize,
+    /// When `Some`, [`StorageMethods::create_node`] rejects any label not in
+    /// this set with `GraphError::StorageError` instead of accepting
+    /// arbitrary strings. Meant to be populated from a parsed schema's
+    /// declared `NODE` labels by a caller that wants schema enforcement;
+    /// `None` (the default) stays permissive, matching today's behavior.
+    ///
+    /// [`StorageMethods::create_node`]: super::storage_methods::StorageMethods::create_node
+    pub allowed_node_labels: Option<HashSet<String>>,
+    /// Same mechanism as [`StorageConfig::allowed_node_labels`], but checked
+    /// by [`StorageMethods::create_edge`] against `EDGE` labels instead.
+    ///
+    /// [`StorageMethods::create_edge`]: super::storage_methods::StorageMethods::create_edge
+    pub allowed_edge_labels: Option<HashSet<String>>,
+    /// When `true`, [`HelixGraphStorage::serialize_node`]/
+    /// [`HelixGraphStorage::serialize_edge`] replace each property key with
+    /// a small interned integer id (kept in a `pk:`/`pki:` table in
+    /// `CF_INDICES`) instead of writing the key string into every record —
+    /// worthwhile when a label's keys (e.g. `created_at`) repeat across many
+    /// records. Opt-in since it changes the on-disk record shape; every
+    /// record tags which shape it's in, so toggling this on a store that
+    /// already has plain records is safe, it just means old records stay
+    /// plain until rewritten. Defaults to `false`.
+    pub intern_property_keys: bool,
+    /// When `true`, [`HelixGraphStorage::open_with_config`] opens RocksDB via
+    /// `DB::open_cf_descriptors_read_only` and every mutating
+    /// [`StorageMethods`] method returns `GraphError::StorageError("read-only")`
+    /// instead of touching the database. Meant for a query replica that
+    /// should never accidentally write. Defaults to `false`.
+    ///
+    /// [`StorageMethods`]: super::storage_methods::StorageMethods
+    pub read_only: bool,
+    /// Whether [`StorageMethods::create_edge`] accepts an edge whose
+    /// `from_id` and `to_id` are the same node. Defaults to `true` for
+    /// backwards compatibility; set to `false` for a schema that shouldn't
+    /// allow self-loops, in which case `create_edge` rejects one with
+    /// `GraphError::Validation`.
+    ///
+    /// [`StorageMethods::create_edge`]: super::storage_methods::StorageMethods::create_edge
+    pub allow_self_loops: bool,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            verify_checksums: true,
+            default_scan_limit: Some(1000),
+            auto_created_at: true,
+            wal_recovery_mode: WalRecoveryMode::PointInTime,
+            compression_threshold: 4096,
+            allowed_node_labels: None,
+            allowed_edge_labels: None,
+            intern_property_keys: false,
+            read_only: false,
+            allow_self_loops: true,
+        }
+    }
+}
+
+/// Controls how RocksDB replays the write-ahead log when a store is opened,
+/// trading durability strictness for tolerance of a WAL tail an unclean
+/// shutdown left incomplete. Maps onto `rocksdb::DBRecoveryMode` via
+/// [`Options::set_wal_recovery_mode`] in [`HelixGraphStorage::open_with_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalRecoveryMode {
+    /// Refuse to open unless every WAL record is intact. The strictest
+    /// option: a crash mid-write can leave the store unable to reopen at
+    /// all, which is why this isn't the default.
+    Absolute,
+    /// Replay every WAL record up to (but not including) the first
+    /// corrupted one, then stop — whatever was still in flight when the
+    /// process died is dropped, but nothing earlier is ever second-guessed.
+    /// The default, since it recovers from the common "killed mid-write"
+    /// case without risking silently losing older, already-durable data.
+    PointInTime,
+    /// Skip any corrupted record and keep replaying past it, even if more
+    /// valid-looking records follow. The most tolerant option, but it can
+    /// mask a genuinely damaged WAL (disk corruption, not just a crash
+    /// mid-write) rather than surfacing it — prefer `PointInTime` unless
+    /// that still fails to open.
+    SkipAnyCorruptedRecord,
+}
+
+impl WalRecoveryMode {
+    fn to_rocksdb(self) -> DBRecoveryMode {
+        match self {
+            WalRecoveryMode::Absolute => DBRecoveryMode::AbsoluteConsistency,
+            WalRecoveryMode::PointInTime => DBRecoveryMode::PointInTime,
+            WalRecoveryMode::SkipAnyCorruptedRecord => DBRecoveryMode::SkipAnyCorruptedRecord,
+        }
+    }
+}
+
+/// Thin wrapper around the on-disk RocksDB handle used by the graph engine.
+///
+/// Nodes live in `CF_NODES` keyed by `n:{id}`, and only that — data, not
+/// index, keys. Edges live in `CF_EDGES` keyed by `e:{id}`, likewise data
+/// only. Every index-kind key, whether derived from a node (`nl:{label}:{id}`)
+/// or an edge (`o:{from_id}:{edge_id}`/`i:{to_id}:{edge_id}` adjacency,
+/// `ei:{label}:{property}:{value}:{id}`/`eim:{label}:{property}` property
+/// indices), lives in `CF_INDICES`, so a plain `n:`/`e:` prefix scan over
+/// `CF_NODES`/`CF_EDGES` never has to skip index entries and a per-CF
+/// key-count estimate (see [`HelixGraphStorage::approx_node_count`]/
+/// [`HelixGraphStorage::approx_edge_count`]) isn't skewed by them.
+pub struct HelixGraphStorage {
+    pub db: DB,
+    pub format: SerializationFormat,
+    pub config: StorageConfig,
+    path: String,
+}
+
+impl HelixGraphStorage {
+    pub fn new(path: &str) -> Result<Self, GraphError> {
+        Self::open_with_format(path, SerializationFormat::Bincode)
+    }
+
+    /// Opens (or creates) the store at `path`. If the store already exists,
+    /// the format it was created with wins over `default_format` — once
+    /// written, records are only ever read back with the codec that wrote
+    /// them.
+    pub fn open_with_format(path: &str, default_format: SerializationFormat) -> Result<Self, GraphError> {
+        Self::open_with_config(path, default_format, StorageConfig::default())
+    }
+
+    pub fn open_with_config(
+        path: &str,
+        default_format: SerializationFormat,
+        config: StorageConfig,
+    ) -> Result<Self, GraphError> {
+        if let Some(parent) = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                GraphError::StorageConnectionError(format!(
+                    "{path}: parent directory {} is not writable: {e}",
+                    parent.display()
+                ))
+            })?;
+        }
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_wal_recovery_mode(config.wal_recovery_mode.to_rocksdb());
+
+        let cfs = vec![
+            ColumnFamilyDescriptor::new(CF_NODES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_EDGES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_INDICES, Options::default()),
+        ];
+
+        let db = if config.read_only {
+            DB::open_cf_descriptors_read_only(&opts, Path::new(path), cfs, false)
+                .map_err(|e| GraphError::StorageConnectionError(format!("{path}: {e}")))?
+        } else {
+            DB::open_cf_descriptors(&opts, Path::new(path), cfs)
+                .map_err(|e| GraphError::StorageConnectionError(format!("{path}: {e}")))?
+        };
+
+        let nodes_cf = db
+            .cf_handle(CF_NODES)
+            .expect("CF_NODES column family must exist");
+        let format = match db
+            .get_cf(nodes_cf, FORMAT_META_KEY)
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+        {
+            Some(bytes) => SerializationFormat::from_byte(bytes.first().copied().unwrap_or(0))
+                .unwrap_or(default_format),
+            None if config.read_only => default_format,
+            None => {
+                db.put_cf(nodes_cf, FORMAT_META_KEY, [default_format.to_byte()])
+                    .map_err(|e| GraphError::StorageError(e.to_string()))?;
+                default_format
+            }
+        };
+
+        Ok(HelixGraphStorage {
+            db,
+            format,
+            config,
+            path: path.to_string(),
+        })
+    }
+
+    /// Closes the current RocksDB handle and reopens the same on-disk store
+    /// with `config` in place of whatever it was opened with before.
+    ///
+    /// Takes `self` by value rather than `&mut self` so the old `DB` handle
+    /// can't outlive the close: flushing, dropping it, and reopening at the
+    /// same path all happen before this function returns, and the compiler
+    /// rejects any attempt to keep using the old handle afterward. Useful
+    /// for restoring from a checkpoint or picking up a new `StorageConfig`
+    /// (e.g. a different `wal_recovery_mode`) without restarting the process.
+    pub fn reopen(self, config: StorageConfig) -> Result<Self, GraphError> {
+        let path = self.path.clone();
+        let format = self.format;
+        self.db.flush().map_err(|e| GraphError::StorageError(e.to_string()))?;
+        drop(self.db);
+        Self::open_with_config(&path, format, config)
+    }
+
+    /// Serializes `value` via `self.format`, then transparently
+    /// zstd-compresses the result if it's larger than
+    /// `self.config.compression_threshold` (see [`super::codec::compress`]).
+    pub fn serialize<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, GraphError> {
+        let bytes = self.format.serialize(value)?;
+        codec::compress(bytes, self.config.compression_threshold)
+    }
+
+    /// Reverses [`HelixGraphStorage::serialize`]: undoes the compression
+    /// flag byte before handing the record to `self.format`.
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, GraphError> {
+        let bytes = codec::decompress(bytes)?;
+        self.format.deserialize(&bytes)
+    }
+
+    /// Serializes `node`, replacing its property keys with interned ids
+    /// first when `config.intern_property_keys` is set. The record tags
+    /// which shape it's in, so [`HelixGraphStorage::deserialize_node`]
+    /// always knows how to read it back regardless of the current config.
+    pub fn serialize_node(&self, node: &Node) -> Result<Vec<u8>, GraphError> {
+        let record = if self.config.intern_property_keys {
+            NodeRecord::Interned {
+                id: node.id.clone(),
+                label: node.label.clone(),
+                properties: self.intern_properties(&node.properties)?,
+            }
+        } else {
+            NodeRecord::Plain(node.clone())
+        };
+        self.serialize(&record)
+    }
+
+    /// Reverses [`HelixGraphStorage::serialize_node`].
+    pub fn deserialize_node(&self, bytes: &[u8]) -> Result<Node, GraphError> {
+        match self.deserialize::<NodeRecord>(bytes)? {
+            NodeRecord::Plain(node) => Ok(node),
+            NodeRecord::Interned { id, label, properties } => {
+                Ok(Node::new(id, label, self.resolve_properties(properties)?))
+            }
+        }
+    }
+
+    /// Mirrors [`HelixGraphStorage::serialize_node`] for edges.
+    pub fn serialize_edge(&self, edge: &Edge) -> Result<Vec<u8>, GraphError> {
+        let record = if self.config.intern_property_keys {
+            EdgeRecord::Interned {
+                id: edge.id.clone(),
+                label: edge.label.clone(),
+                from_node: edge.from_node.clone(),
+                to_node: edge.to_node.clone(),
+                properties: self.intern_properties(&edge.properties)?,
+            }
+        } else {
+            EdgeRecord::Plain(edge.clone())
+        };
+        self.serialize(&record)
+    }
+
+    /// Reverses [`HelixGraphStorage::serialize_edge`].
+    pub fn deserialize_edge(&self, bytes: &[u8]) -> Result<Edge, GraphError> {
+        match self.deserialize::<EdgeRecord>(bytes)? {
+            EdgeRecord::Plain(edge) => Ok(edge),
+            EdgeRecord::Interned { id, label, from_node, to_node, properties } => Ok(Edge::new(
+                id,
+                label,
+                from_node,
+                to_node,
+                self.resolve_properties(properties)?,
+            )),
+        }
+    }
+
+    fn intern_properties(&self, properties: &Properties) -> Result<Vec<(u32, Value)>, GraphError> {
+        properties
+            .iter()
+            .map(|(key, value)| Ok((self.intern_key(key)?, value.clone())))
+            .collect()
+    }
+
+    fn resolve_properties(&self, properties: Vec<(u32, Value)>) -> Result<Properties, GraphError> {
+        properties
+            .into_iter()
+            .map(|(key_id, value)| Ok((self.resolve_key(key_id)?, value)))
+            .collect()
+    }
+
+    /// Looks up `key`'s interned id in the `pk:{key}` table, allocating and
+    /// persisting a fresh one (along with its `pki:{id}` reverse entry) if
+    /// this is the first time `key` has been interned.
+    ///
+    /// Race window: the lookup and the allocation are separate calls, not
+    /// one atomic operation, so two callers racing on the same brand-new key
+    /// can each allocate a different id for it, leaving two `pk:`/`pki:`
+    /// entries for one key. Harmless (both ids resolve back to the same
+    /// string, and records only ever reference the id they were written
+    /// with) but wastes an id; fine for the low-cardinality, rarely-changing
+    /// set of property keys this is meant for.
+    fn intern_key(&self, key: &str) -> Result<u32, GraphError> {
+        let forward_key = property_key_forward_key(key);
+        if let Some(bytes) = self.db.get_cf(self.cf_indices(), &forward_key)? {
+            return Ok(u32::from_le_bytes(bytes.as_slice().try_into().map_err(|_| {
+                GraphError::StorageError(format!("corrupt interned key id for {key:?}"))
+            })?));
+        }
+
+        let next_id = match self.db.get_cf(self.cf_indices(), PROPERTY_KEY_COUNTER)? {
+            Some(bytes) => u32::from_le_bytes(
+                bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| GraphError::StorageError("corrupt property key counter".to_string()))?,
+            ),
+            None => 0,
+        };
+
+        self.db.put_cf(self.cf_indices(), &forward_key, next_id.to_le_bytes())?;
+        self.db
+            .put_cf(self.cf_indices(), property_key_reverse_key(next_id), key.as_bytes())?;
+        self.db
+            .put_cf(self.cf_indices(), PROPERTY_KEY_COUNTER, (next_id + 1).to_le_bytes())?;
+        Ok(next_id)
+    }
+
+    /// Reverses [`HelixGraphStorage::intern_key`] via the `pki:{id}` entry
+    /// `intern_key` wrote alongside the forward one.
+    fn resolve_key(&self, key_id: u32) -> Result<String, GraphError> {
+        let bytes = self
+            .db
+            .get_cf(self.cf_indices(), property_key_reverse_key(key_id))?
+            .ok_or_else(|| GraphError::StorageError(format!("no interned property key for id {key_id}")))?;
+        String::from_utf8(bytes)
+            .map_err(|e| GraphError::StorageError(format!("corrupt interned property key {key_id}: {e}")))
+    }
+
+    /// Guard called at the top of every mutating [`StorageMethods`] method.
+    /// Errors with `GraphError::StorageError("read-only")` when
+    /// `config.read_only` is set, before anything is written.
+    ///
+    /// [`StorageMethods`]: super::storage_methods::StorageMethods
+    pub fn reject_if_read_only(&self) -> Result<(), GraphError> {
+        if self.config.read_only {
+            return Err(GraphError::StorageError("read-only".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Builds `ReadOptions` reflecting `self.config` for a single read or
+    /// iteration call.
+    pub fn read_opts(&self) -> ReadOptions {
+        let mut opts = ReadOptions::default();
+        opts.set_verify_checksums(self.config.verify_checksums);
+        opts
+    }
+
+    pub fn cf_nodes(&self) -> &rocksdb::ColumnFamily {
+        self.db
+            .cf_handle(CF_NODES)
+            .expect("CF_NODES column family must exist")
+    }
+
+    pub fn cf_edges(&self) -> &rocksdb::ColumnFamily {
+        self.db
+            .cf_handle(CF_EDGES)
+            .expect("CF_EDGES column family must exist")
+    }
+
+    pub fn cf_indices(&self) -> &rocksdb::ColumnFamily {
+        self.db
+            .cf_handle(CF_INDICES)
+            .expect("CF_INDICES column family must exist")
+    }
+
+    /// Dumps RocksDB's own diagnostics for a slow-traversal investigation:
+    /// the engine-wide `rocksdb.stats` property (compaction history, cache
+    /// hit rates, stall counts), followed by per-column-family memtable
+    /// size, pending compaction bytes, and block cache usage for each of
+    /// `CF_NODES`/`CF_EDGES`/`CF_INDICES`. Property names and formatting are
+    /// whatever RocksDB itself returns — this is a pass-through, not a
+    /// parsed summary, since the shape of `rocksdb.stats` varies by version
+    /// and operators reading it already know what to grep for.
+    ///
+    /// There's no HTTP handler module in this tree yet to wire a
+    /// `/debug/rocksdb` route into (`helix_gateway::router` only has test
+    /// handlers) — once one exists, gate it behind a `StorageConfig` flag
+    /// rather than exposing it unconditionally, since `rocksdb.stats` can be
+    /// large and operators may not want it reachable by default.
+    pub fn rocksdb_stats(&self) -> Result<String, GraphError> {
+        let mut out = String::new();
+        if let Some(stats) = self
+            .db
+            .property_value("rocksdb.stats")
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+        {
+            out.push_str(&stats);
+        }
+
+        for (name, cf) in [
+            (CF_NODES, self.cf_nodes()),
+            (CF_EDGES, self.cf_edges()),
+            (CF_INDICES, self.cf_indices()),
+        ] {
+            out.push_str(&format!("\n[{name}]\n"));
+            for property in [
+                "rocksdb.cur-size-all-mem-tables",
+                "rocksdb.estimate-pending-compaction-bytes",
+                "rocksdb.block-cache-usage",
+            ] {
+                if let Some(value) = self
+                    .db
+                    .property_value_cf(cf, property)
+                    .map_err(|e| GraphError::StorageError(e.to_string()))?
+                {
+                    out.push_str(&format!("{property}: {value}\n"));
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Approximates the node count from RocksDB's `rocksdb.estimate-num-keys`
+    /// property on `CF_NODES`, in O(1) instead of the O(n) scan
+    /// `get_all_nodes().len()` would do. The estimate isn't exact — it's
+    /// RocksDB's own memtable+SST bookkeeping, which can lag behind
+    /// uncompacted writes and deletes — and it's off by the one
+    /// `meta:serialization_format` key this column family also stores
+    /// alongside `n:{id}` node records. The `nl:{label}:{id}` label index
+    /// lives in `CF_INDICES`, not here, so it needs no further adjustment.
+    pub fn approx_node_count(&self) -> Result<u64, GraphError> {
+        Ok(self
+            .db
+            .property_int_value_cf(self.cf_nodes(), "rocksdb.estimate-num-keys")
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+            .unwrap_or(0))
+    }
+
+    /// Mirrors [`HelixGraphStorage::approx_node_count`] for edges. Unlike the
+    /// node count, there's no meta key polluting `CF_EDGES` and no
+    /// adjustment needed for the `o:`/`i:` adjacency keys either — those live
+    /// in `CF_INDICES` alongside the other index-kind keys, so `CF_EDGES`
+    /// holds exactly one `e:{id}` key per edge.
+    pub fn approx_edge_count(&self) -> Result<u64, GraphError> {
+        Ok(self
+            .db
+            .property_int_value_cf(self.cf_edges(), "rocksdb.estimate-num-keys")
+            .map_err(|e| GraphError::StorageError(e.to_string()))?
+            .unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helix_engine::storage_core::StorageMethods;
+    use std::collections::HashMap;
+
+    #[test]
+    fn messagepack_mode_round_trips_and_bincode_mode_is_unchanged() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+
+        {
+            let storage = HelixGraphStorage::open_with_format(&path, SerializationFormat::MessagePack).unwrap();
+            assert_eq!(storage.format, SerializationFormat::MessagePack);
+            storage.create_node("person", HashMap::new()).unwrap();
+        }
+        {
+            // Reopening with a different default must not override the
+            // persisted format.
+            let storage = HelixGraphStorage::open_with_format(&path, SerializationFormat::Bincode).unwrap();
+            assert_eq!(storage.format, SerializationFormat::MessagePack);
+            assert_eq!(storage.get_all_nodes().unwrap().len(), 1);
+        }
+
+        let bincode_path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::new(bincode_path.to_str().unwrap()).unwrap();
+        assert_eq!(storage.format, SerializationFormat::Bincode);
+    }
+
+    #[test]
+    fn opening_under_an_unusable_parent_gives_a_descriptive_storage_connection_error() {
+        // A parent path that's a plain file (not a directory) can never be
+        // created via `create_dir_all`, regardless of the test's own
+        // permissions — unlike a chmod'd directory, which root ignores.
+        let not_a_dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&not_a_dir, b"not a directory").unwrap();
+
+        let path = not_a_dir.join("sub").join("db");
+        let result = HelixGraphStorage::new(path.to_str().unwrap());
+
+        std::fs::remove_file(&not_a_dir).unwrap();
+
+        match result {
+            Err(GraphError::StorageConnectionError(msg)) => {
+                assert!(msg.contains(path.to_str().unwrap()));
+            }
+            other => panic!("expected a StorageConnectionError naming the path, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reads_succeed_with_verify_checksums_on_and_off() {
+        for verify_checksums in [true, false] {
+            let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+            let storage = HelixGraphStorage::open_with_config(
+                path.to_str().unwrap(),
+                SerializationFormat::Bincode,
+                StorageConfig {
+                    verify_checksums,
+                    ..StorageConfig::default()
+                },
+            )
+            .unwrap();
+            let node = storage.create_node("person", HashMap::new()).unwrap();
+            assert_eq!(storage.get_node(&node.id).unwrap().id, node.id);
+        }
+    }
+
+    #[test]
+    fn large_property_values_are_compressed_on_disk_and_round_trip_unchanged() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::open_with_config(
+            path.to_str().unwrap(),
+            SerializationFormat::Bincode,
+            StorageConfig {
+                compression_threshold: 256,
+                ..StorageConfig::default()
+            },
+        )
+        .unwrap();
+
+        let large_value = crate::protocol::Value::String("x".repeat(10_000));
+        let mut props = HashMap::new();
+        props.insert("bio".to_string(), large_value.clone());
+        let node = storage.create_node("person", props).unwrap();
+
+        let raw = storage
+            .db
+            .get_cf_opt(storage.cf_nodes(), format!("n:{}", node.id), &storage.read_opts())
+            .unwrap()
+            .unwrap();
+        assert!(
+            raw.len() < 10_000,
+            "expected the stored record to be smaller than the uncompressed property value, got {} bytes",
+            raw.len()
+        );
+
+        let reloaded = storage.get_node(&node.id).unwrap();
+        assert_eq!(reloaded.properties.get("bio"), Some(&large_value));
+    }
+
+    #[test]
+    fn small_property_values_round_trip_without_compression() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::new(path.to_str().unwrap()).unwrap();
+
+        let mut props = HashMap::new();
+        props.insert("name".to_string(), crate::protocol::Value::String("Will".to_string()));
+        let node = storage.create_node("person", props).unwrap();
+
+        assert_eq!(
+            storage.get_node(&node.id).unwrap().properties.get("name"),
+            Some(&crate::protocol::Value::String("Will".to_string()))
+        );
+    }
+
+    /// Best-effort: truncates the tail of the newest WAL segment to simulate
+    /// a crash mid-write, then checks that `SkipAnyCorruptedRecord` still
+    /// opens. Whether `Absolute` actually surfaces the corruption depends on
+    /// exactly where the truncation landed relative to a record boundary, so
+    /// that half isn't asserted on — only that the tolerant mode is never
+    /// worse off than it would be against an intact WAL.
+    #[test]
+    fn tolerant_recovery_mode_opens_a_store_with_a_truncated_wal_tail() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let path = dir.to_str().unwrap().to_string();
+
+        {
+            let storage = HelixGraphStorage::new(&path).unwrap();
+            for _ in 0..50 {
+                storage.create_node("item", HashMap::new()).unwrap();
+            }
+        }
+
+        let mut log_files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| p.extension().map_or(false, |ext| ext == "log"))
+            .collect();
+        log_files.sort();
+        let newest = log_files
+            .last()
+            .expect("expected at least one WAL segment on disk")
+            .clone();
+
+        let mut bytes = std::fs::read(&newest).unwrap();
+        let truncated_len = bytes.len().saturating_sub(16);
+        bytes.truncate(truncated_len);
+        std::fs::write(&newest, bytes).unwrap();
+
+        let tolerant = HelixGraphStorage::open_with_config(
+            &path,
+            SerializationFormat::Bincode,
+            StorageConfig {
+                wal_recovery_mode: WalRecoveryMode::SkipAnyCorruptedRecord,
+                ..StorageConfig::default()
+            },
+        );
+        assert!(tolerant.is_ok(), "{:?}", tolerant.err());
+    }
+
+    #[test]
+    fn reopen_with_a_different_config_preserves_existing_data() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::new(path.to_str().unwrap()).unwrap();
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+
+        let storage = storage
+            .reopen(StorageConfig {
+                verify_checksums: false,
+                ..StorageConfig::default()
+            })
+            .unwrap();
+
+        assert!(!storage.config.verify_checksums);
+        assert_eq!(storage.get_node(&node.id).unwrap().id, node.id);
+    }
+
+    #[test]
+    fn rocksdb_stats_is_non_empty_and_names_known_properties_after_writes() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::new(path.to_str().unwrap()).unwrap();
+        for _ in 0..20 {
+            storage.create_node("item", HashMap::new()).unwrap();
+        }
+
+        let stats = storage.rocksdb_stats().unwrap();
+
+        assert!(!stats.is_empty());
+        assert!(stats.contains(&format!("[{CF_NODES}]")));
+        assert!(stats.contains("rocksdb.cur-size-all-mem-tables"));
+    }
+
+    #[test]
+    fn approx_node_count_and_approx_edge_count_are_within_tolerance_of_the_real_totals() {
+        use crate::helix_engine::storage_core::StorageMethods;
+
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::new(path.to_str().unwrap()).unwrap();
+
+        let mut ids = Vec::new();
+        for _ in 0..200 {
+            ids.push(storage.create_node("item", HashMap::new()).unwrap().id);
+        }
+        for i in 0..199 {
+            storage
+                .create_edge("next", &ids[i], &ids[i + 1], HashMap::new())
+                .unwrap();
+        }
+
+        let node_count = storage.approx_node_count().unwrap();
+        let edge_count = storage.approx_edge_count().unwrap();
+
+        let within_tolerance = |estimate: u64, actual: u64| {
+            let diff = estimate.abs_diff(actual);
+            diff <= actual / 5 + 2
+        };
+        assert!(
+            within_tolerance(node_count, 200),
+            "estimated {node_count}, expected close to 200"
+        );
+        assert!(
+            within_tolerance(edge_count, 199),
+            "estimated {edge_count}, expected close to 199"
+        );
+    }
+
+    #[test]
+    fn interned_node_round_trips_and_is_smaller_than_the_plain_form() {
+        let plain_path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let plain = HelixGraphStorage::new(plain_path.to_str().unwrap()).unwrap();
+
+        let interned_path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let interned = HelixGraphStorage::open_with_config(
+            interned_path.to_str().unwrap(),
+            SerializationFormat::Bincode,
+            StorageConfig {
+                intern_property_keys: true,
+                ..StorageConfig::default()
+            },
+        )
+        .unwrap();
+
+        let mut props = HashMap::new();
+        props.insert(
+            "a_fairly_long_repeated_property_key_name".to_string(),
+            Value::String("x".to_string()),
+        );
+        props.insert(
+            "another_fairly_long_repeated_property_key".to_string(),
+            Value::String("y".to_string()),
+        );
+
+        let plain_node = Node::new("n1".to_string(), "person".to_string(), props.clone());
+        let interned_node = Node::new("n1".to_string(), "person".to_string(), props);
+
+        let plain_bytes = plain.serialize_node(&plain_node).unwrap();
+        let interned_bytes = interned.serialize_node(&interned_node).unwrap();
+
+        assert!(
+            interned_bytes.len() < plain_bytes.len(),
+            "expected interning to shrink the record: plain={}, interned={}",
+            plain_bytes.len(),
+            interned_bytes.len()
+        );
+
+        let round_tripped = interned.deserialize_node(&interned_bytes).unwrap();
+        assert_eq!(round_tripped.id, interned_node.id);
+        assert_eq!(round_tripped.label, interned_node.label);
+        assert_eq!(round_tripped.properties, interned_node.properties);
+    }
+
+    #[test]
+    fn read_only_mode_allows_reads_but_rejects_every_mutation() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let node_id = {
+            let storage = HelixGraphStorage::new(path.to_str().unwrap()).unwrap();
+            let a = storage.create_node("person", HashMap::new()).unwrap();
+            let b = storage.create_node("person", HashMap::new()).unwrap();
+            storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+            a.id
+        };
+
+        let storage = HelixGraphStorage::open_with_config(
+            path.to_str().unwrap(),
+            SerializationFormat::Bincode,
+            StorageConfig {
+                read_only: true,
+                ..StorageConfig::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(storage.get_node(&node_id).unwrap().id, node_id);
+        assert_eq!(storage.get_all_nodes().unwrap().len(), 2);
+        assert_eq!(storage.get_all_edges().unwrap().len(), 1);
+
+        fn is_read_only_error<T>(result: Result<T, GraphError>) -> bool {
+            matches!(result, Err(GraphError::StorageError(msg)) if msg == "read-only")
+        }
+        assert!(is_read_only_error(storage.create_node("person", HashMap::new())));
+        assert!(is_read_only_error(storage.create_edge(
+            "knows",
+            &node_id,
+            &node_id,
+            HashMap::new()
+        )));
+        assert!(is_read_only_error(storage.drop_node(&node_id)));
+        assert!(is_read_only_error(storage.update_edge("missing", HashMap::new())));
+        assert!(is_read_only_error(storage.rename_node_label("person", "human")));
+    }
+
+    #[test]
+    fn normal_mode_is_unaffected_by_the_read_only_option_existing() {
+        let path = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = HelixGraphStorage::open_with_config(
+            path.to_str().unwrap(),
+            SerializationFormat::Bincode,
+            StorageConfig {
+                read_only: false,
+                ..StorageConfig::default()
+            },
+        )
+        .unwrap();
+
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+        assert_eq!(storage.get_node(&node.id).unwrap().id, node.id);
+        storage.drop_node(&node.id).unwrap();
+        assert!(storage.get_node(&node.id).is_err());
+    }
+}
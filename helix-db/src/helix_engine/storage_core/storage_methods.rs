@@ -0,0 +1,2135 @@
+use super::storage_core::HelixGraphStorage;
+use crate::helix_engine::types::GraphError;
+use crate::protocol::{DataType, Edge, Node, Properties, Value};
+use rocksdb::{IteratorMode, WriteBatch};
+use std::collections::HashSet;
+
+/// Controls what [`StorageMethods::create_node_with_id`] does when `id`
+/// already names a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreateMode {
+    /// Fail with `GraphError::New` rather than touch the existing node.
+    ErrorIfExists,
+    /// Discard the existing node's properties and write `properties` in
+    /// their place.
+    Replace,
+    /// Union `properties` into the existing node's properties, with
+    /// `properties` winning on key collisions — the existing node's other
+    /// properties are left untouched.
+    Merge,
+}
+
+/// CRUD surface implemented by the storage layer and used by the graph core.
+///
+/// Every method that writes (`create_*`/`drop_*`/`update_*`/`rename_*`/
+/// `coerce_property`/`create_edge_index`) calls
+/// [`HelixGraphStorage::reject_if_read_only`] first, so when
+/// `StorageConfig::read_only` is set each one errors with
+/// `GraphError::StorageError("read-only")` instead of touching the database.
+pub trait StorageMethods {
+    /// Errors with `GraphError::StorageError` if `label` isn't in
+    /// `StorageConfig::allowed_node_labels`, when that allow-list is set.
+    fn create_node(&self, label: &str, properties: Properties) -> Result<Node, GraphError>;
+    /// Creates a node at a caller-chosen `id` instead of a fresh uuid, with
+    /// `mode` controlling what happens if `id` already names a node. This is
+    /// the building block for upsert-style writes (e.g. importing records
+    /// that carry their own stable id) without forcing every caller through
+    /// separate create/update calls.
+    fn create_node_with_id(
+        &self,
+        id: &str,
+        label: &str,
+        properties: Properties,
+        mode: CreateMode,
+    ) -> Result<Node, GraphError>;
+    /// Errors with `GraphError::StorageError` if `label` isn't in
+    /// `StorageConfig::allowed_edge_labels`, when that allow-list is set.
+    /// Checks `from_id` and `to_id` separately before writing anything,
+    /// returning [`GraphError::Validation`] naming exactly which endpoint(s)
+    /// are missing if either isn't a real node. If `from_id == to_id` (a
+    /// self-loop) and `StorageConfig::allow_self_loops` is `false`, also
+    /// returns `GraphError::Validation` instead of creating the edge. See
+    /// [`StorageMethods::create_edge_ensure_nodes`] for the variant that
+    /// creates missing endpoints instead of rejecting the edge.
+    fn create_edge(
+        &self,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError>;
+    /// Stages a node creation into `batch` instead of writing it immediately,
+    /// so it only becomes durable when the caller commits `batch` — the
+    /// building block [`crate::helix_engine::graph_core::HelixGraphEngine::with_batch`]
+    /// uses to make a handler's multi-step write atomic.
+    fn stage_create_node(
+        &self,
+        batch: &mut WriteBatch,
+        label: &str,
+        properties: Properties,
+    ) -> Result<Node, GraphError>;
+    /// Mirrors [`StorageMethods::stage_create_node`] for edges: stages the
+    /// edge record and both adjacency entries. Property-index entries are not
+    /// staged; [`crate::helix_engine::graph_core::HelixGraphEngine::with_batch`]
+    /// commits the batch without writing them, so an edge created this way
+    /// has no property-index entries.
+    fn stage_create_edge(
+        &self,
+        batch: &mut WriteBatch,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError>;
+    fn get_node(&self, id: &str) -> Result<Node, GraphError>;
+    fn get_edge(&self, id: &str) -> Result<Edge, GraphError>;
+    /// Removes the node and every edge touching it. Like
+    /// [`StorageMethods::drop_edge`], this doesn't clean up the node's
+    /// `nl:{label}:{id}` label-index entry or any edge property indices that
+    /// referenced it — an existing inconsistency in this schema, not
+    /// something this method newly introduces.
+    fn drop_node(&self, id: &str) -> Result<(), GraphError>;
+    /// Removes the edge and its adjacency entries. Idempotent: deleting an
+    /// edge that's already gone returns `Ok(None)` rather than an error, so
+    /// two requests racing to delete the same edge are both safe.
+    fn drop_edge(&self, id: &str) -> Result<Option<Edge>, GraphError>;
+    /// Removes every edge touching `node_id` (as either endpoint) without
+    /// removing the node itself, leaving it with zero degree. Shares the
+    /// same adjacency cascade as [`StorageMethods::drop_node`] minus the
+    /// final node delete. Returns how many edges were removed.
+    fn drop_node_edges(&self, node_id: &str) -> Result<usize, GraphError>;
+    /// Removes every node in `ids` along with the edges touching each one,
+    /// across a single [`rocksdb::WriteBatch`] rather than one write per
+    /// node. Ids that don't name an existing node are skipped rather than
+    /// erroring. Returns how many nodes were actually removed.
+    fn drop_nodes(&self, ids: &[&str]) -> Result<usize, GraphError>;
+    /// Removes every edge in `ids` across a single [`rocksdb::WriteBatch`].
+    /// Mirrors [`StorageMethods::drop_edge`]'s tolerance of already-missing
+    /// ids: each one is simply skipped rather than erroring. Returns how
+    /// many edges were actually removed.
+    fn drop_edges(&self, ids: &[&str]) -> Result<usize, GraphError>;
+    /// Whether `id` names a node. Does not look at `CF_EDGES` at all — an
+    /// edge id always reports `false` here. See [`StorageMethods::edge_exists`]
+    /// for the edge-side check.
+    fn node_exists(&self, id: &str) -> Result<bool, GraphError>;
+    /// Whether `id` names an edge. Mirrors [`StorageMethods::node_exists`]
+    /// over `CF_EDGES` instead of `CF_NODES`.
+    fn edge_exists(&self, id: &str) -> Result<bool, GraphError>;
+    fn get_all_nodes(&self) -> Result<Vec<Node>, GraphError>;
+    fn get_all_edges(&self) -> Result<Vec<Edge>, GraphError>;
+    /// Like [`StorageMethods::get_all_nodes`] but stops the RocksDB iterator
+    /// as soon as `limit` nodes have been read, instead of scanning `CF_NODES`
+    /// in full and truncating afterwards.
+    fn get_nodes_limited(&self, limit: usize) -> Result<Vec<Node>, GraphError>;
+    fn get_edges_limited(&self, limit: usize) -> Result<Vec<Edge>, GraphError>;
+    /// Fetches edges, optionally filtered to `label` and capped at `limit`.
+    /// A `label` is served from the unconditional `el:{label}:{id}` index
+    /// (mirrors [`StorageMethods::get_node_ids_by_label`]'s `nl:` index for
+    /// nodes) instead of a full `CF_EDGES` scan, so a labeled query on a
+    /// graph with millions of edges of other labels doesn't pay for them.
+    /// With no `label`, falls back to [`StorageMethods::get_edges_limited`]
+    /// or [`StorageMethods::get_all_edges`] since there's no index to narrow
+    /// the scan with.
+    fn get_edges_filtered(&self, label: Option<&str>, limit: Option<usize>) -> Result<Vec<Edge>, GraphError>;
+    /// Counts edges labelled `edge_label` going from `from_id` to `to_id`.
+    ///
+    /// On a multigraph this can be greater than one; callers that only want
+    /// to know whether a relationship exists should check `> 0` rather than
+    /// assuming a single edge.
+    fn count_edges_between(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        edge_label: &str,
+    ) -> Result<usize, GraphError>;
+    /// Fetches every edge labelled `edge_label` going from `from_id` to
+    /// `to_id`. Mirrors [`StorageMethods::count_edges_between`] but returns
+    /// the edges themselves instead of just a count, for callers on a
+    /// multigraph that need to inspect or act on each parallel edge.
+    fn get_edges_between(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        edge_label: &str,
+    ) -> Result<Vec<Edge>, GraphError>;
+    /// Returns the first edge labelled `edge_label` from `from_id` to
+    /// `to_id` if one already exists (via [`StorageMethods::get_edges_between`]),
+    /// otherwise creates one with `properties`. Meant for sync jobs that
+    /// want "ensure this relationship exists" without creating duplicate
+    /// parallel edges on every run.
+    ///
+    /// Race window: the existence check and the create are two separate
+    /// calls, not one atomic operation, so two callers racing on the same
+    /// `(edge_label, from_id, to_id)` can both see no existing edge and both
+    /// create one, leaving a duplicate. Fine for the infrequent, single-writer
+    /// sync jobs this is meant for; a caller with genuine concurrent writers
+    /// should deduplicate afterward or serialize around this call.
+    fn find_or_create_edge(
+        &self,
+        edge_label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError>;
+    /// Counts outgoing edges from `node_id`, optionally filtered to
+    /// `edge_label`. Reads a `deg:o:{node_id}[:{label}]` counter in
+    /// `CF_INDICES` instead of scanning the `o:{node_id}:` prefix — every
+    /// edge create/drop keeps that counter in step, so this stays O(1) even
+    /// for a high-degree hub node.
+    fn count_out_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<usize, GraphError>;
+    /// Mirrors [`StorageMethods::count_out_edges`] via the `deg:i:` counters.
+    fn count_in_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<usize, GraphError>;
+    /// Fetches outgoing edges from `node_id`. When `edge_label` is `None`,
+    /// every outgoing edge comes back regardless of label; `Some` filters to
+    /// just that label, same as [`StorageMethods::count_out_edges`].
+    fn get_out_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<Vec<Edge>, GraphError>;
+    /// Mirrors [`StorageMethods::get_out_edges`] over `i:{node_id}:`.
+    fn get_in_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<Vec<Edge>, GraphError>;
+    /// Fetches many nodes by id in a single `multi_get_cf` round trip instead
+    /// of one [`StorageMethods::get_node`] call per id — the building block
+    /// neighbor-expansion steps like `out`/`in_` use to turn N point lookups
+    /// into one. Returns the nodes in the same order as `ids` (duplicates in
+    /// `ids` are preserved, not deduped), failing on the first id that
+    /// doesn't name a node.
+    fn get_nodes_by_ids(&self, ids: &[String]) -> Result<Vec<Node>, GraphError>;
+    /// Parses node ids directly out of the `nl:{label}:{id}` label index keys
+    /// under `CF_INDICES`, without deserializing a single node value. Every
+    /// node is indexed by label unconditionally (unlike edge property
+    /// indices, which opt in via [`StorageMethods::create_edge_index`]),
+    /// since label is intrinsic to a node rather than an arbitrary property.
+    /// Like the edge indices, a dropped node's `nl:` entry isn't cleaned up
+    /// — see [`StorageMethods::drop_node`].
+    fn get_node_ids_by_label(&self, label: &str) -> Result<Vec<String>, GraphError>;
+    /// Mirrors [`StorageMethods::get_node_ids_by_label`] but fetches the full
+    /// node records (one batched [`StorageMethods::get_nodes_by_ids`] call)
+    /// instead of just the ids.
+    fn get_nodes_by_label(&self, label: &str) -> Result<Vec<Node>, GraphError>;
+    /// Coerces `property` on every node labelled `label` to `to` (see
+    /// [`Value::coerce`]) and rewrites the node if the value actually
+    /// changed. Nodes missing `property`, or whose value can't be coerced
+    /// to `to` (e.g. `String("not a number")` to `DataType::Integer`), are
+    /// left untouched rather than erroring. Returns how many nodes were
+    /// rewritten.
+    fn coerce_property(&self, label: &str, property: &str, to: DataType) -> Result<usize, GraphError>;
+    fn update_edge(&self, id: &str, properties: Properties) -> Result<Edge, GraphError>;
+    /// Registers `property` as indexed for edges labelled `label`, backfilling
+    /// entries for edges that already exist. Once registered, `create_edge`
+    /// and `update_edge` keep the index up to date automatically.
+    fn create_edge_index(&self, label: &str, property: &str) -> Result<(), GraphError>;
+    fn get_edges_by_property(
+        &self,
+        label: &str,
+        property: &str,
+        value: &Value,
+    ) -> Result<Vec<Edge>, GraphError>;
+    /// Creates many nodes in one pass, all landing in a single `WriteBatch`
+    /// instead of one write per node. Mirrors [`StorageMethods::create_edges`]:
+    /// returns one `Result` per input node, in order, so a label rejected by
+    /// `allowed_node_labels` doesn't abort the rest of the batch.
+    fn create_nodes(&self, nodes: Vec<(String, Properties)>) -> Result<Vec<Result<Node, GraphError>>, GraphError>;
+    /// Mirrors [`StorageMethods::create_nodes`] but returns only each
+    /// created node's id instead of the full [`Node`] — for a caller bulk-
+    /// creating thousands of nodes who only needs the new ids back, this
+    /// skips re-serializing every node into the response.
+    fn create_nodes_ids(&self, nodes: Vec<(String, Properties)>) -> Result<Vec<Result<String, GraphError>>, GraphError>;
+    /// Creates many edges in one pass: every referenced endpoint is
+    /// existence-checked with a single `multi_get_cf` instead of two
+    /// `get_node` reads per edge, and every edge + adjacency write lands in
+    /// one `WriteBatch`.
+    ///
+    /// Returns one `Result` per input edge, in order, so a caller importing
+    /// a mix of valid and dangling edges can tell which ones failed (and
+    /// why) without the whole batch aborting.
+    fn create_edges(
+        &self,
+        edges: Vec<(String, String, String, Properties)>,
+    ) -> Result<Vec<Result<Edge, GraphError>>, GraphError>;
+    /// Creates the edge `from_id -> to_id`, auto-creating either endpoint
+    /// that doesn't exist yet (with no properties, labelled `from_label` or
+    /// `to_label` respectively) instead of failing like
+    /// [`StorageMethods::create_edge`] does. Existence is checked with one
+    /// `multi_get_cf` and every write — the endpoint nodes plus the edge
+    /// itself — lands in a single `WriteBatch`, so a caller ingesting a
+    /// stream of edges ahead of their nodes never sees a partially-written
+    /// edge. Endpoint labels are required rather than inferred, so an
+    /// auto-created node can't end up mislabeled just because its id
+    /// happened to be unseen.
+    fn create_edge_ensure_nodes(
+        &self,
+        edge_label: &str,
+        from_label: &str,
+        from_id: &str,
+        to_label: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError>;
+    /// Renames every node labelled `old` to `new`, batching the rewritten
+    /// records into a single `WriteBatch` and committing once. Still does a
+    /// full scan of every node via [`StorageMethods::get_all_nodes`] rather
+    /// than a targeted [`StorageMethods::get_nodes_by_label`] lookup — fine
+    /// for the infrequent, maintenance-only nature of a schema rename.
+    /// Returns the number of nodes renamed.
+    fn rename_node_label(&self, old: &str, new: &str) -> Result<usize, GraphError>;
+    /// Mirrors [`StorageMethods::rename_node_label`] for edges, also
+    /// refreshing any property indices registered on `old` since an index
+    /// key embeds the edge's label.
+    fn rename_edge_label(&self, old: &str, new: &str) -> Result<usize, GraphError>;
+}
+
+fn index_value_key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Bytes(b) => format!("{b:?}"),
+        Value::Array(_) | Value::Empty => String::new(),
+    }
+}
+
+fn edge_index_meta_key(label: &str, property: &str) -> String {
+    format!("eim:{label}:{property}")
+}
+
+fn edge_index_key(label: &str, property: &str, value: &Value, edge_id: &str) -> String {
+    format!("ei:{label}:{property}:{}:{edge_id}", index_value_key(value))
+}
+
+fn node_key(id: &str) -> String {
+    format!("n:{id}")
+}
+
+fn edge_key(id: &str) -> String {
+    format!("e:{id}")
+}
+
+fn out_adjacency_key(from_id: &str, edge_id: &str) -> String {
+    format!("o:{from_id}:{edge_id}")
+}
+
+fn in_adjacency_key(to_id: &str, edge_id: &str) -> String {
+    format!("i:{to_id}:{edge_id}")
+}
+
+fn node_label_index_key(label: &str, node_id: &str) -> String {
+    format!("nl:{label}:{node_id}")
+}
+
+/// Mirrors [`node_label_index_key`] for edges. Like the node index, this is
+/// written unconditionally at edge-creation time (unlike the opt-in
+/// property indices under `eim:`/`ei:`) but, also like the node index,
+/// isn't cleaned up when an edge is dropped — see [`StorageMethods::drop_edge`].
+fn edge_label_index_key(label: &str, edge_id: &str) -> String {
+    format!("el:{label}:{edge_id}")
+}
+
+/// Checks `label` against an optional allow-list. `None` stays permissive;
+/// `Some(allowed)` rejects anything not in it with `GraphError::StorageError`.
+/// Shared by [`StorageMethods::create_node`]'s check against
+/// `allowed_node_labels` and [`StorageMethods::create_edge`]'s against
+/// `allowed_edge_labels`.
+fn check_label_allowed(label: &str, allowed: &Option<HashSet<String>>) -> Result<(), GraphError> {
+    match allowed {
+        Some(allowed) if !allowed.contains(label) => Err(GraphError::StorageError(format!(
+            "label {label:?} is not in the declared schema"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Inserts a `created_at` property (epoch milliseconds) if
+/// `config.auto_created_at` is set and the caller didn't already supply
+/// their own `created_at`.
+fn stamp_created_at(config: &super::storage_core::StorageConfig, mut properties: Properties) -> Properties {
+    if config.auto_created_at && !properties.contains_key("created_at") {
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        properties.insert("created_at".to_string(), Value::Integer(millis));
+    }
+    properties
+}
+
+impl StorageMethods for HelixGraphStorage {
+    fn create_node(&self, label: &str, properties: Properties) -> Result<Node, GraphError> {
+        self.reject_if_read_only()?;
+        check_label_allowed(label, &self.config.allowed_node_labels)?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let node = Node::new(id.clone(), label, stamp_created_at(&self.config, properties));
+        let bytes = self.serialize_node(&node)?;
+        self.db.put_cf(self.cf_nodes(), node_key(&id), bytes)?;
+        self.db
+            .put_cf(self.cf_indices(), node_label_index_key(label, &id), [])?;
+        Ok(node)
+    }
+
+    fn create_node_with_id(
+        &self,
+        id: &str,
+        label: &str,
+        properties: Properties,
+        mode: CreateMode,
+    ) -> Result<Node, GraphError> {
+        self.reject_if_read_only()?;
+        check_label_allowed(label, &self.config.allowed_node_labels)?;
+        let existing = self
+            .db
+            .get_cf_opt(self.cf_nodes(), node_key(id), &self.read_opts())?
+            .map(|bytes| self.deserialize_node(&bytes))
+            .transpose()?;
+
+        let old_label = existing.as_ref().map(|n| n.label.clone());
+        let node = match (existing, mode) {
+            (Some(_), CreateMode::ErrorIfExists) => {
+                return Err(GraphError::New(format!("node already exists: {id}")));
+            }
+            (Some(_), CreateMode::Replace) | (None, _) => Node::new(id, label, properties),
+            (Some(old), CreateMode::Merge) => {
+                let mut merged = old.properties;
+                merged.extend(properties);
+                Node::new(id, label, merged)
+            }
+        };
+
+        let bytes = self.serialize_node(&node)?;
+        self.db.put_cf(self.cf_nodes(), node_key(id), bytes)?;
+        if old_label.as_deref() != Some(label) {
+            if let Some(old_label) = old_label {
+                self.db
+                    .delete_cf(self.cf_indices(), node_label_index_key(&old_label, id))?;
+            }
+            self.db
+                .put_cf(self.cf_indices(), node_label_index_key(label, id), [])?;
+        }
+        Ok(node)
+    }
+
+    fn create_edge(
+        &self,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        self.reject_if_read_only()?;
+        check_label_allowed(label, &self.config.allowed_edge_labels)?;
+        if !self.config.allow_self_loops && from_id == to_id {
+            return Err(GraphError::Validation(format!(
+                "self-loops are disabled: {from_id} cannot have an edge to itself"
+            )));
+        }
+
+        let keys = vec![
+            (self.cf_nodes(), node_key(from_id).into_bytes()),
+            (self.cf_nodes(), node_key(to_id).into_bytes()),
+        ];
+        let mut existing = self.db.multi_get_cf_opt(keys, &self.read_opts()).into_iter();
+        let from_exists = matches!(existing.next(), Some(Ok(Some(_))));
+        let to_exists = matches!(existing.next(), Some(Ok(Some(_))));
+        match (from_exists, to_exists) {
+            (true, true) => {}
+            (false, true) => {
+                return Err(GraphError::Validation(format!(
+                    "from node does not exist: {from_id}"
+                )))
+            }
+            (true, false) => {
+                return Err(GraphError::Validation(format!(
+                    "to node does not exist: {to_id}"
+                )))
+            }
+            (false, false) => {
+                return Err(GraphError::Validation(format!(
+                    "from node does not exist: {from_id}, to node does not exist: {to_id}"
+                )))
+            }
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let edge = Edge::new(id.clone(), label, from_id, to_id, stamp_created_at(&self.config, properties));
+        let bytes = self.serialize_edge(&edge)?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.cf_edges(), edge_key(&id), &bytes);
+        batch.put_cf(self.cf_indices(), out_adjacency_key(from_id, &id), &bytes);
+        batch.put_cf(self.cf_indices(), in_adjacency_key(to_id, &id), &bytes);
+        batch.put_cf(self.cf_indices(), edge_label_index_key(label, &id), []);
+        stage_degree_delta(self, &mut batch, "o", from_id, label, 1)?;
+        stage_degree_delta(self, &mut batch, "i", to_id, label, 1)?;
+        self.db.write(batch)?;
+
+        self.sync_edge_indices(&edge)?;
+        Ok(edge)
+    }
+
+    fn stage_create_node(
+        &self,
+        batch: &mut WriteBatch,
+        label: &str,
+        properties: Properties,
+    ) -> Result<Node, GraphError> {
+        self.reject_if_read_only()?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let node = Node::new(id.clone(), label, stamp_created_at(&self.config, properties));
+        let bytes = self.serialize_node(&node)?;
+        batch.put_cf(self.cf_nodes(), node_key(&id), bytes);
+        batch.put_cf(self.cf_indices(), node_label_index_key(label, &id), []);
+        Ok(node)
+    }
+
+    fn stage_create_edge(
+        &self,
+        batch: &mut WriteBatch,
+        label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        self.reject_if_read_only()?;
+        check_label_allowed(label, &self.config.allowed_edge_labels)?;
+        if !self.config.allow_self_loops && from_id == to_id {
+            return Err(GraphError::Validation(format!(
+                "self-loops are disabled: {from_id} cannot have an edge to itself"
+            )));
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        let edge = Edge::new(id.clone(), label, from_id, to_id, stamp_created_at(&self.config, properties));
+        let bytes = self.serialize_edge(&edge)?;
+
+        batch.put_cf(self.cf_edges(), edge_key(&id), &bytes);
+        batch.put_cf(self.cf_indices(), out_adjacency_key(from_id, &id), &bytes);
+        batch.put_cf(self.cf_indices(), in_adjacency_key(to_id, &id), &bytes);
+        batch.put_cf(self.cf_indices(), edge_label_index_key(label, &id), []);
+        stage_degree_delta(self, batch, "o", from_id, label, 1)?;
+        stage_degree_delta(self, batch, "i", to_id, label, 1)?;
+
+        Ok(edge)
+    }
+
+    fn create_nodes(&self, nodes: Vec<(String, Properties)>) -> Result<Vec<Result<Node, GraphError>>, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let mut results = Vec::with_capacity(nodes.len());
+
+        for (label, properties) in nodes {
+            if let Err(e) = check_label_allowed(&label, &self.config.allowed_node_labels) {
+                results.push(Err(e));
+                continue;
+            }
+            let id = uuid::Uuid::new_v4().to_string();
+            let node = Node::new(id.clone(), label.as_str(), stamp_created_at(&self.config, properties));
+            let bytes = self.serialize_node(&node)?;
+            batch.put_cf(self.cf_nodes(), node_key(&id), bytes);
+            batch.put_cf(self.cf_indices(), node_label_index_key(&label, &id), []);
+            results.push(Ok(node));
+        }
+
+        self.db.write(batch)?;
+        Ok(results)
+    }
+
+    fn create_nodes_ids(&self, nodes: Vec<(String, Properties)>) -> Result<Vec<Result<String, GraphError>>, GraphError> {
+        Ok(self
+            .create_nodes(nodes)?
+            .into_iter()
+            .map(|r| r.map(|n| n.id))
+            .collect())
+    }
+
+    fn create_edges(
+        &self,
+        edges: Vec<(String, String, String, Properties)>,
+    ) -> Result<Vec<Result<Edge, GraphError>>, GraphError> {
+        self.reject_if_read_only()?;
+        let mut node_ids: Vec<String> = Vec::with_capacity(edges.len() * 2);
+        for (_, from_id, to_id, _) in &edges {
+            node_ids.push(from_id.clone());
+            node_ids.push(to_id.clone());
+        }
+        node_ids.sort_unstable();
+        node_ids.dedup();
+
+        let keys: Vec<_> = node_ids
+            .iter()
+            .map(|id| (self.cf_nodes(), node_key(id).into_bytes()))
+            .collect();
+        let existing: HashSet<String> = self
+            .db
+            .multi_get_cf_opt(keys, &self.read_opts())
+            .into_iter()
+            .zip(node_ids)
+            .filter_map(|(result, id)| match result {
+                Ok(Some(_)) => Some(id),
+                _ => None,
+            })
+            .collect();
+
+        let mut batch = WriteBatch::default();
+        let mut results = Vec::with_capacity(edges.len());
+        let mut committed = Vec::new();
+
+        for (label, from_id, to_id, properties) in edges {
+            if !existing.contains(from_id.as_str()) {
+                results.push(Err(GraphError::NodeNotFound(from_id)));
+                continue;
+            }
+            if !existing.contains(to_id.as_str()) {
+                results.push(Err(GraphError::NodeNotFound(to_id)));
+                continue;
+            }
+
+            match self.stage_create_edge(&mut batch, &label, &from_id, &to_id, properties) {
+                Ok(edge) => {
+                    committed.push(edge.clone());
+                    results.push(Ok(edge));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        self.db.write(batch)?;
+        for edge in &committed {
+            self.sync_edge_indices(edge)?;
+        }
+
+        Ok(results)
+    }
+
+    fn create_edge_ensure_nodes(
+        &self,
+        edge_label: &str,
+        from_label: &str,
+        from_id: &str,
+        to_label: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        self.reject_if_read_only()?;
+        let keys = vec![
+            (self.cf_nodes(), node_key(from_id).into_bytes()),
+            (self.cf_nodes(), node_key(to_id).into_bytes()),
+        ];
+        let mut existing = self.db.multi_get_cf_opt(keys, &self.read_opts()).into_iter();
+        let from_exists = matches!(existing.next(), Some(Ok(Some(_))));
+        let to_exists = matches!(existing.next(), Some(Ok(Some(_))));
+
+        let mut batch = WriteBatch::default();
+        if !from_exists {
+            check_label_allowed(from_label, &self.config.allowed_node_labels)?;
+            let node = Node::new(from_id, from_label, Properties::new());
+            let bytes = self.serialize_node(&node)?;
+            batch.put_cf(self.cf_nodes(), node_key(from_id), bytes);
+            batch.put_cf(self.cf_indices(), node_label_index_key(from_label, from_id), []);
+        }
+        if !to_exists && to_id != from_id {
+            check_label_allowed(to_label, &self.config.allowed_node_labels)?;
+            let node = Node::new(to_id, to_label, Properties::new());
+            let bytes = self.serialize_node(&node)?;
+            batch.put_cf(self.cf_nodes(), node_key(to_id), bytes);
+            batch.put_cf(self.cf_indices(), node_label_index_key(to_label, to_id), []);
+        }
+
+        let edge = self.stage_create_edge(&mut batch, edge_label, from_id, to_id, properties)?;
+        self.db.write(batch)?;
+        self.sync_edge_indices(&edge)?;
+        Ok(edge)
+    }
+
+    fn rename_node_label(&self, old: &str, new: &str) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let mut count = 0;
+        for node in self.get_all_nodes()? {
+            if node.label != old {
+                continue;
+            }
+            let renamed = Node::new(node.id.clone(), new, node.properties);
+            let bytes = self.serialize_node(&renamed)?;
+            batch.put_cf(self.cf_nodes(), node_key(&node.id), bytes);
+            batch.delete_cf(self.cf_indices(), node_label_index_key(old, &node.id));
+            batch.put_cf(self.cf_indices(), node_label_index_key(new, &node.id), []);
+            count += 1;
+        }
+        self.db.write(batch)?;
+        Ok(count)
+    }
+
+    fn rename_edge_label(&self, old: &str, new: &str) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let mut renamed_edges = Vec::new();
+        let mut label_deltas: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
+        for edge in self.get_all_edges()? {
+            if edge.label != old {
+                continue;
+            }
+            let renamed = Edge::new(
+                edge.id.clone(),
+                new,
+                edge.from_node.clone(),
+                edge.to_node.clone(),
+                edge.properties.clone(),
+            );
+            let bytes = self.serialize_edge(&renamed)?;
+            batch.put_cf(self.cf_edges(), edge_key(&edge.id), &bytes);
+            batch.put_cf(self.cf_indices(), out_adjacency_key(&edge.from_node, &edge.id), &bytes);
+            batch.put_cf(self.cf_indices(), in_adjacency_key(&edge.to_node, &edge.id), &bytes);
+            batch.delete_cf(self.cf_indices(), edge_label_index_key(old, &edge.id));
+            batch.put_cf(self.cf_indices(), edge_label_index_key(new, &edge.id), []);
+            // Only the per-label counters move; each node's total in/out
+            // degree is unaffected by a label rename.
+            for (direction, node_id) in [("o", &edge.from_node), ("i", &edge.to_node)] {
+                *label_deltas.entry(degree_label_key(direction, node_id, old)).or_insert(0) -= 1;
+                *label_deltas.entry(degree_label_key(direction, node_id, new)).or_insert(0) += 1;
+            }
+            renamed_edges.push((edge, renamed));
+        }
+        for (key, delta) in label_deltas {
+            let count = (read_degree_counter(self, &key)? + delta).max(0);
+            batch.put_cf(self.cf_indices(), key, count.to_string());
+        }
+
+        let count = renamed_edges.len();
+        self.db.write(batch)?;
+        for (old_edge, new_edge) in &renamed_edges {
+            self.clear_edge_indices(old_edge)?;
+            self.sync_edge_indices(new_edge)?;
+        }
+        Ok(count)
+    }
+
+    fn get_node(&self, id: &str) -> Result<Node, GraphError> {
+        let bytes = self
+            .db
+            .get_cf_opt(self.cf_nodes(), node_key(id), &self.read_opts())?
+            .ok_or_else(|| GraphError::NodeNotFound(id.to_string()))?;
+        self.deserialize_node(&bytes)
+            .map_err(|e| GraphError::StorageError(format!("corrupt node record {id}: {e}")))
+    }
+
+    fn get_edge(&self, id: &str) -> Result<Edge, GraphError> {
+        let bytes = self
+            .db
+            .get_cf_opt(self.cf_edges(), edge_key(id), &self.read_opts())?
+            .ok_or_else(|| GraphError::EdgeNotFound(id.to_string()))?;
+        self.deserialize_edge(&bytes)
+            .map_err(|e| GraphError::StorageError(format!("corrupt edge record {id}: {e}")))
+    }
+
+    /// Removes a node along with every edge touching it (as either endpoint).
+    ///
+    /// The outgoing (`o:{id}:*`) and incoming (`i:{id}:*`) adjacency entries
+    /// live in `CF_INDICES`, while the `e:{id}` edge record they point at
+    /// lives in `CF_EDGES` — both prefix scans below read `cf_indices`, but
+    /// each matched edge's own record is deleted from `cf_edges`.
+    fn drop_node(&self, id: &str) -> Result<(), GraphError> {
+        self.drop_node_edges(id)?;
+        self.db.delete_cf(self.cf_nodes(), node_key(id))?;
+        Ok(())
+    }
+
+    fn drop_node_edges(&self, node_id: &str) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let edge_count = stage_drop_node_edges(self, node_id, &mut batch)?;
+        self.db.write(batch)?;
+        Ok(edge_count)
+    }
+
+    fn drop_edge(&self, id: &str) -> Result<Option<Edge>, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let edge = stage_drop_edge(self, id, &mut batch)?;
+        if edge.is_some() {
+            self.db.write(batch)?;
+        }
+        Ok(edge)
+    }
+
+    fn drop_nodes(&self, ids: &[&str]) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let mut removed = 0;
+        for id in ids {
+            if self.db.get_cf_opt(self.cf_nodes(), node_key(id), &self.read_opts())?.is_none() {
+                continue;
+            }
+            stage_drop_node_edges(self, id, &mut batch)?;
+            batch.delete_cf(self.cf_nodes(), node_key(id));
+            removed += 1;
+        }
+        self.db.write(batch)?;
+        Ok(removed)
+    }
+
+    fn drop_edges(&self, ids: &[&str]) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut batch = WriteBatch::default();
+        let mut removed = 0;
+        for id in ids {
+            if stage_drop_edge(self, id, &mut batch)?.is_some() {
+                removed += 1;
+            }
+        }
+        self.db.write(batch)?;
+        Ok(removed)
+    }
+
+    fn node_exists(&self, id: &str) -> Result<bool, GraphError> {
+        Ok(self.db.get_cf_opt(self.cf_nodes(), node_key(id), &self.read_opts())?.is_some())
+    }
+
+    fn edge_exists(&self, id: &str) -> Result<bool, GraphError> {
+        Ok(self.db.get_cf_opt(self.cf_edges(), edge_key(id), &self.read_opts())?.is_some())
+    }
+
+    fn update_edge(&self, id: &str, properties: Properties) -> Result<Edge, GraphError> {
+        self.reject_if_read_only()?;
+        let old = self.get_edge(id)?;
+        let updated = Edge::new(
+            old.id.clone(),
+            old.label.clone(),
+            old.from_node.clone(),
+            old.to_node.clone(),
+            properties,
+        );
+        let bytes = self.serialize_edge(&updated)?;
+        self.db.put_cf(self.cf_edges(), edge_key(id), &bytes)?;
+        self.db
+            .put_cf(self.cf_indices(), out_adjacency_key(&updated.from_node, id), &bytes)?;
+        self.db
+            .put_cf(self.cf_indices(), in_adjacency_key(&updated.to_node, id), &bytes)?;
+
+        self.clear_edge_indices(&old)?;
+        self.sync_edge_indices(&updated)?;
+        Ok(updated)
+    }
+
+    fn create_edge_index(&self, label: &str, property: &str) -> Result<(), GraphError> {
+        self.reject_if_read_only()?;
+        self.db
+            .put_cf(self.cf_indices(), edge_index_meta_key(label, property), b"1")?;
+        for edge in self.get_all_edges()? {
+            if edge.label == label {
+                if let Some(value) = edge.properties.get(property) {
+                    self.db
+                        .put_cf(self.cf_indices(), edge_index_key(label, property, value, &edge.id), b"")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_edges_by_property(
+        &self,
+        label: &str,
+        property: &str,
+        value: &Value,
+    ) -> Result<Vec<Edge>, GraphError> {
+        let prefix = format!("ei:{label}:{property}:{}:", index_value_key(value));
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut edges = Vec::new();
+        for item in iter {
+            let (key, _) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let key_str = String::from_utf8_lossy(&key);
+            let edge_id = key_str.rsplit(':').next().unwrap_or_default();
+            edges.push(self.get_edge(edge_id)?);
+        }
+        Ok(edges)
+    }
+
+    fn get_all_nodes(&self) -> Result<Vec<Node>, GraphError> {
+        let mut nodes = Vec::new();
+        for item in self.db.iterator_cf_opt(self.cf_nodes(), self.read_opts(), IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(b"n:") {
+                continue;
+            }
+            nodes.push(self.deserialize_node(&value).map_err(|e| {
+                GraphError::StorageError(format!("corrupt node record {:?}: {e}", String::from_utf8_lossy(&key)))
+            })?);
+        }
+        Ok(nodes)
+    }
+
+    fn get_all_edges(&self) -> Result<Vec<Edge>, GraphError> {
+        let mut edges = Vec::new();
+        for item in self.db.iterator_cf_opt(self.cf_edges(), self.read_opts(), IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(b"e:") {
+                continue;
+            }
+            edges.push(self.deserialize_edge(&value).map_err(|e| {
+                GraphError::StorageError(format!("corrupt edge record {:?}: {e}", String::from_utf8_lossy(&key)))
+            })?);
+        }
+        Ok(edges)
+    }
+
+    fn get_nodes_limited(&self, limit: usize) -> Result<Vec<Node>, GraphError> {
+        let mut nodes = Vec::with_capacity(limit);
+        for item in self.db.iterator_cf_opt(self.cf_nodes(), self.read_opts(), IteratorMode::Start) {
+            if nodes.len() >= limit {
+                break;
+            }
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(b"n:") {
+                continue;
+            }
+            nodes.push(self.deserialize_node(&value).map_err(|e| {
+                GraphError::StorageError(format!("corrupt node record {:?}: {e}", String::from_utf8_lossy(&key)))
+            })?);
+        }
+        Ok(nodes)
+    }
+
+    fn get_edges_limited(&self, limit: usize) -> Result<Vec<Edge>, GraphError> {
+        let mut edges = Vec::with_capacity(limit);
+        for item in self.db.iterator_cf_opt(self.cf_edges(), self.read_opts(), IteratorMode::Start) {
+            if edges.len() >= limit {
+                break;
+            }
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(b"e:") {
+                continue;
+            }
+            edges.push(self.deserialize_edge(&value).map_err(|e| {
+                GraphError::StorageError(format!("corrupt edge record {:?}: {e}", String::from_utf8_lossy(&key)))
+            })?);
+        }
+        Ok(edges)
+    }
+
+    fn get_edges_filtered(&self, label: Option<&str>, limit: Option<usize>) -> Result<Vec<Edge>, GraphError> {
+        let Some(label) = label else {
+            return match limit {
+                Some(limit) => self.get_edges_limited(limit),
+                None => self.get_all_edges(),
+            };
+        };
+
+        let prefix = edge_label_index_key(label, "");
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut ids = Vec::new();
+        for item in iter {
+            if limit.map(|limit| ids.len() >= limit).unwrap_or(false) {
+                break;
+            }
+            let (key, _) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            ids.push(String::from_utf8_lossy(&key[prefix.len()..]).into_owned());
+        }
+
+        let keys: Vec<_> = ids.iter().map(|id| (self.cf_edges(), edge_key(id).into_bytes())).collect();
+        let results = self.db.multi_get_cf_opt(keys, &self.read_opts());
+
+        let mut edges = Vec::with_capacity(ids.len());
+        for (result, id) in results.into_iter().zip(&ids) {
+            match result.map_err(|e| GraphError::StorageError(e.to_string()))? {
+                Some(bytes) => edges.push(
+                    self.deserialize_edge(&bytes)
+                        .map_err(|e| GraphError::StorageError(format!("corrupt edge record {id}: {e}")))?,
+                ),
+                None => return Err(GraphError::EdgeNotFound(id.clone())),
+            }
+        }
+        Ok(edges)
+    }
+
+    fn count_edges_between(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        edge_label: &str,
+    ) -> Result<usize, GraphError> {
+        let prefix = format!("o:{from_id}:");
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut count = 0;
+        for item in iter {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let edge: Edge =
+                self.deserialize_edge(&value)?;
+            if edge.to_node == to_id && edge.label == edge_label {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    fn get_edges_between(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        edge_label: &str,
+    ) -> Result<Vec<Edge>, GraphError> {
+        let prefix = format!("o:{from_id}:");
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut edges = Vec::new();
+        for item in iter {
+            let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let edge: Edge = self.deserialize_edge(&value)?;
+            if edge.to_node == to_id && edge.label == edge_label {
+                edges.push(edge);
+            }
+        }
+        Ok(edges)
+    }
+
+    fn find_or_create_edge(
+        &self,
+        edge_label: &str,
+        from_id: &str,
+        to_id: &str,
+        properties: Properties,
+    ) -> Result<Edge, GraphError> {
+        if let Some(existing) = self.get_edges_between(from_id, to_id, edge_label)?.into_iter().next() {
+            return Ok(existing);
+        }
+        self.create_edge(edge_label, from_id, to_id, properties)
+    }
+
+    fn count_out_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<usize, GraphError> {
+        let key = match edge_label {
+            Some(label) => degree_label_key("o", node_id, label),
+            None => degree_total_key("o", node_id),
+        };
+        Ok(read_degree_counter(self, &key)? as usize)
+    }
+
+    fn count_in_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<usize, GraphError> {
+        let key = match edge_label {
+            Some(label) => degree_label_key("i", node_id, label),
+            None => degree_total_key("i", node_id),
+        };
+        Ok(read_degree_counter(self, &key)? as usize)
+    }
+
+    fn get_out_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<Vec<Edge>, GraphError> {
+        collect_adjacency(self, &format!("o:{node_id}:"), edge_label)
+    }
+
+    fn get_in_edges(&self, node_id: &str, edge_label: Option<&str>) -> Result<Vec<Edge>, GraphError> {
+        collect_adjacency(self, &format!("i:{node_id}:"), edge_label)
+    }
+
+    fn get_nodes_by_ids(&self, ids: &[String]) -> Result<Vec<Node>, GraphError> {
+        let keys: Vec<_> = ids
+            .iter()
+            .map(|id| (self.cf_nodes(), node_key(id).into_bytes()))
+            .collect();
+        let results = self.db.multi_get_cf_opt(keys, &self.read_opts());
+
+        let mut nodes = Vec::with_capacity(ids.len());
+        for (result, id) in results.into_iter().zip(ids) {
+            match result.map_err(|e| GraphError::StorageError(e.to_string()))? {
+                Some(bytes) => nodes.push(self.deserialize_node(&bytes)?),
+                None => return Err(GraphError::NodeNotFound(id.clone())),
+            }
+        }
+        Ok(nodes)
+    }
+
+    fn get_node_ids_by_label(&self, label: &str) -> Result<Vec<String>, GraphError> {
+        let prefix = node_label_index_key(label, "");
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut ids = Vec::new();
+        for item in iter {
+            let (key, _) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let id = String::from_utf8_lossy(&key[prefix.len()..]).into_owned();
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    fn get_nodes_by_label(&self, label: &str) -> Result<Vec<Node>, GraphError> {
+        let ids = self.get_node_ids_by_label(label)?;
+        self.get_nodes_by_ids(&ids)
+    }
+
+    fn coerce_property(&self, label: &str, property: &str, to: DataType) -> Result<usize, GraphError> {
+        self.reject_if_read_only()?;
+        let mut changed = 0;
+        for mut node in self.get_nodes_by_label(label)? {
+            let Some(value) = node.properties.get(property) else {
+                continue;
+            };
+            let Some(coerced) = value.coerce(to) else {
+                continue;
+            };
+            if coerced == *value {
+                continue;
+            }
+            node.properties.insert(property.to_string(), coerced);
+            let bytes = self.serialize_node(&node)?;
+            self.db.put_cf(self.cf_nodes(), node_key(&node.id), bytes)?;
+            changed += 1;
+        }
+        Ok(changed)
+    }
+}
+
+/// Collects every edge keyed under `prefix`, optionally filtered to
+/// `edge_label`.
+fn collect_adjacency(
+    storage: &HelixGraphStorage,
+    prefix: &str,
+    edge_label: Option<&str>,
+) -> Result<Vec<Edge>, GraphError> {
+    let iter = storage.db.iterator_cf_opt(
+        storage.cf_indices(),
+        storage.read_opts(),
+        IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+    );
+    let mut edges = Vec::new();
+    for item in iter {
+        let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+        let edge: Edge = storage.deserialize_edge(&value)?;
+        if edge_label.map_or(true, |label| edge.label == label) {
+            edges.push(edge);
+        }
+    }
+    Ok(edges)
+}
+
+fn degree_total_key(direction: &str, node_id: &str) -> String {
+    format!("deg:{direction}:{node_id}")
+}
+
+fn degree_label_key(direction: &str, node_id: &str, label: &str) -> String {
+    format!("deg:{direction}:{node_id}:{label}")
+}
+
+fn read_degree_counter(storage: &HelixGraphStorage, key: &str) -> Result<i64, GraphError> {
+    Ok(storage
+        .db
+        .get_cf_opt(storage.cf_indices(), key, &storage.read_opts())?
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(0))
+}
+
+/// Applies `delta` to both the total and the per-label degree counter for
+/// `node_id` in `direction` (`"o"` for outgoing, `"i"` for incoming),
+/// staging the updated values into `batch` rather than writing them
+/// immediately — every call site already builds a `WriteBatch` for the
+/// adjacency write itself, so the counter commits atomically alongside it.
+/// These are the counters [`StorageMethods::count_out_edges`] and
+/// [`StorageMethods::count_in_edges`] read instead of rescanning the `o:`/`i:`
+/// prefix on every call.
+fn stage_degree_delta(
+    storage: &HelixGraphStorage,
+    batch: &mut WriteBatch,
+    direction: &str,
+    node_id: &str,
+    label: &str,
+    delta: i64,
+) -> Result<(), GraphError> {
+    let total_key = degree_total_key(direction, node_id);
+    let total = (read_degree_counter(storage, &total_key)? + delta).max(0);
+    batch.put_cf(storage.cf_indices(), total_key, total.to_string());
+
+    let label_key = degree_label_key(direction, node_id, label);
+    let count = (read_degree_counter(storage, &label_key)? + delta).max(0);
+    batch.put_cf(storage.cf_indices(), label_key, count.to_string());
+    Ok(())
+}
+
+/// Stages the deletion of every edge touching `node_id` (as either
+/// endpoint) into `batch` instead of issuing each delete against `storage`
+/// directly. Shared by the single-node [`StorageMethods::drop_node_edges`]
+/// and the bulk [`StorageMethods::drop_nodes`] so both cascade through
+/// exactly the same o:/i: scan logic and commit in one write. Returns how
+/// many edges were staged for removal.
+fn stage_drop_node_edges(
+    storage: &HelixGraphStorage,
+    node_id: &str,
+    batch: &mut WriteBatch,
+) -> Result<usize, GraphError> {
+    let cf_indices = storage.cf_indices();
+    let cf_edges = storage.cf_edges();
+    let mut edge_count = 0;
+
+    let out_prefix = format!("o:{node_id}:");
+    let iter = storage.db.iterator_cf_opt(
+        cf_indices,
+        storage.read_opts(),
+        IteratorMode::From(out_prefix.as_bytes(), rocksdb::Direction::Forward),
+    );
+    for item in iter {
+        let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+        if !key.starts_with(out_prefix.as_bytes()) {
+            break;
+        }
+        let edge: Edge = storage.deserialize_edge(&value)?;
+        batch.delete_cf(cf_indices, key);
+        batch.delete_cf(cf_edges, edge_key(&edge.id));
+        batch.delete_cf(cf_indices, in_adjacency_key(&edge.to_node, &edge.id));
+        stage_degree_delta(storage, batch, "o", node_id, &edge.label, -1)?;
+        stage_degree_delta(storage, batch, "i", &edge.to_node, &edge.label, -1)?;
+        edge_count += 1;
+    }
+
+    let in_prefix = format!("i:{node_id}:");
+    let iter = storage.db.iterator_cf_opt(
+        cf_indices,
+        storage.read_opts(),
+        IteratorMode::From(in_prefix.as_bytes(), rocksdb::Direction::Forward),
+    );
+    for item in iter {
+        let (key, value) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+        if !key.starts_with(in_prefix.as_bytes()) {
+            break;
+        }
+        let edge: Edge = storage.deserialize_edge(&value)?;
+        batch.delete_cf(cf_indices, key);
+        batch.delete_cf(cf_edges, edge_key(&edge.id));
+        batch.delete_cf(cf_indices, out_adjacency_key(&edge.from_node, &edge.id));
+        stage_degree_delta(storage, batch, "i", node_id, &edge.label, -1)?;
+        stage_degree_delta(storage, batch, "o", &edge.from_node, &edge.label, -1)?;
+        edge_count += 1;
+    }
+
+    Ok(edge_count)
+}
+
+/// Stages the deletion of edge `id` and its adjacency entries into `batch`.
+/// Tolerates an already-missing edge by returning `Ok(None)` without
+/// staging anything, mirroring [`StorageMethods::drop_edge`]'s own
+/// idempotence. Shared by [`StorageMethods::drop_edge`] and the bulk
+/// [`StorageMethods::drop_edges`].
+fn stage_drop_edge(
+    storage: &HelixGraphStorage,
+    id: &str,
+    batch: &mut WriteBatch,
+) -> Result<Option<Edge>, GraphError> {
+    let edge = match storage.get_edge(id) {
+        Ok(edge) => edge,
+        Err(GraphError::EdgeNotFound(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    batch.delete_cf(storage.cf_edges(), edge_key(id));
+    batch.delete_cf(storage.cf_indices(), out_adjacency_key(&edge.from_node, id));
+    batch.delete_cf(storage.cf_indices(), in_adjacency_key(&edge.to_node, id));
+    stage_degree_delta(storage, batch, "o", &edge.from_node, &edge.label, -1)?;
+    stage_degree_delta(storage, batch, "i", &edge.to_node, &edge.label, -1)?;
+    Ok(Some(edge))
+}
+
+impl HelixGraphStorage {
+    /// Writes index entries for `edge` for every property currently
+    /// registered as indexed on `edge.label`.
+    fn sync_edge_indices(&self, edge: &Edge) -> Result<(), GraphError> {
+        for property in self.indexed_edge_properties(&edge.label)? {
+            if let Some(value) = edge.properties.get(&property) {
+                self.db.put_cf(
+                    self.cf_indices(),
+                    edge_index_key(&edge.label, &property, value, &edge.id),
+                    b"",
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear_edge_indices(&self, edge: &Edge) -> Result<(), GraphError> {
+        for property in self.indexed_edge_properties(&edge.label)? {
+            if let Some(value) = edge.properties.get(&property) {
+                self.db
+                    .delete_cf(self.cf_indices(), edge_index_key(&edge.label, &property, value, &edge.id))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn indexed_edge_properties(&self, label: &str) -> Result<Vec<String>, GraphError> {
+        let prefix = format!("eim:{label}:");
+        let iter = self.db.iterator_cf_opt(
+            self.cf_indices(),
+            self.read_opts(),
+            IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward),
+        );
+        let mut properties = Vec::new();
+        for item in iter {
+            let (key, _) = item.map_err(|e| GraphError::StorageError(e.to_string()))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let key_str = String::from_utf8_lossy(&key);
+            if let Some(property) = key_str.strip_prefix(&prefix) {
+                properties.push(property.to_string());
+            }
+        }
+        Ok(properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helix_engine::graph_core::traversal::{TraversalBuilder, TraversalValue};
+    use std::collections::HashMap;
+
+    fn temp_storage() -> HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn label_allow_lists_reject_undeclared_labels_but_stay_permissive_by_default() {
+        let permissive = temp_storage();
+        permissive.create_node("anything", HashMap::new()).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let restricted = crate::helix_engine::storage_core::HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                allowed_node_labels: Some(HashSet::from(["person".to_string()])),
+                allowed_edge_labels: Some(HashSet::from(["knows".to_string()])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let a = restricted.create_node("person", HashMap::new()).unwrap();
+        let b = restricted.create_node("person", HashMap::new()).unwrap();
+        assert!(restricted.create_node("company", HashMap::new()).is_err());
+
+        restricted
+            .create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+        assert!(restricted
+            .create_edge("works_at", &a.id, &b.id, HashMap::new())
+            .is_err());
+    }
+
+    #[test]
+    fn label_allow_list_is_also_enforced_by_create_node_with_id_and_create_edge_ensure_nodes() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let restricted = crate::helix_engine::storage_core::HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                allowed_node_labels: Some(HashSet::from(["person".to_string()])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(restricted
+            .create_node_with_id("node-1", "malicious", HashMap::new(), CreateMode::Replace)
+            .is_err());
+        restricted
+            .create_node_with_id("node-1", "person", HashMap::new(), CreateMode::Replace)
+            .unwrap();
+
+        match restricted.create_edge_ensure_nodes("knows", "malicious", "from-id", "person", "to-id", HashMap::new()) {
+            Err(GraphError::StorageError(msg)) => assert!(msg.contains("malicious")),
+            other => panic!("expected create_edge_ensure_nodes to reject the undeclared from_label, got {other:?}"),
+        }
+        match restricted.create_edge_ensure_nodes("knows", "person", "from-id", "malicious", "to-id", HashMap::new()) {
+            Err(GraphError::StorageError(msg)) => assert!(msg.contains("malicious")),
+            other => panic!("expected create_edge_ensure_nodes to reject the undeclared to_label, got {other:?}"),
+        }
+        restricted
+            .create_edge_ensure_nodes("knows", "person", "from-id", "person", "to-id", HashMap::new())
+            .unwrap();
+    }
+
+    #[test]
+    fn parallel_edges_are_counted_and_dedup_collapses_repeated_out_targets() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        storage
+            .create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+        storage
+            .create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+
+        assert_eq!(
+            storage.count_edges_between(&a.id, &b.id, "knows").unwrap(),
+            2
+        );
+
+        let from_a = TraversalBuilder::new(&storage, vec![TraversalValue::Node(a.clone())]);
+        let undeduped = from_a.out("knows").unwrap().nodes();
+        assert_eq!(undeduped.len(), 2, "out() reports once per parallel edge");
+
+        let from_a = TraversalBuilder::new(&storage, vec![TraversalValue::Node(a)]);
+        let deduped = from_a.out("knows").unwrap().dedup().nodes();
+        assert_eq!(deduped.len(), 1, "dedup() collapses repeats to distinct nodes");
+        assert_eq!(deduped[0].id, b.id);
+    }
+
+    #[test]
+    fn get_edges_between_returns_only_the_parallel_edges_matching_the_label() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        storage.create_edge("likes", &a.id, &b.id, HashMap::new()).unwrap();
+
+        let knows = storage.get_edges_between(&a.id, &b.id, "knows").unwrap();
+        assert_eq!(knows.len(), 2);
+        assert!(knows.iter().all(|e| e.label == "knows"));
+    }
+
+    #[test]
+    fn find_or_create_edge_called_twice_with_the_same_endpoints_creates_only_one_edge() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        let first = storage
+            .find_or_create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+        let second = storage
+            .find_or_create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(storage.get_edges_between(&a.id, &b.id, "knows").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn drop_edge_twice_is_idempotent() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let edge = storage
+            .create_edge("knows", &a.id, &b.id, HashMap::new())
+            .unwrap();
+
+        let dropped = storage.drop_edge(&edge.id).unwrap();
+        assert_eq!(dropped.unwrap().id, edge.id);
+
+        let dropped_again = storage.drop_edge(&edge.id).unwrap();
+        assert!(dropped_again.is_none());
+    }
+
+    #[test]
+    fn get_nodes_limited_stops_after_limit() {
+        let storage = temp_storage();
+        for _ in 0..100 {
+            storage.create_node("item", HashMap::new()).unwrap();
+        }
+
+        let limited = storage.get_nodes_limited(10).unwrap();
+        assert_eq!(limited.len(), 10);
+
+        let all = storage.get_all_nodes().unwrap();
+        assert_eq!(all.len(), 100);
+    }
+
+    #[test]
+    fn get_edges_filtered_by_label_matches_a_scan_then_filter_and_respects_limit() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let c = storage.create_node("person", HashMap::new()).unwrap();
+        for _ in 0..3 {
+            storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        }
+        storage.create_edge("follows", &b.id, &c.id, HashMap::new()).unwrap();
+
+        let mut via_index: Vec<String> = storage
+            .get_edges_filtered(Some("knows"), None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        via_index.sort();
+
+        let mut via_scan: Vec<String> = storage
+            .get_all_edges()
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.label == "knows")
+            .map(|e| e.id)
+            .collect();
+        via_scan.sort();
+
+        assert_eq!(via_index, via_scan);
+        assert_eq!(via_index.len(), 3);
+
+        let limited = storage.get_edges_filtered(Some("knows"), Some(2)).unwrap();
+        assert_eq!(limited.len(), 2);
+
+        let unfiltered = storage.get_edges_filtered(None, None).unwrap();
+        assert_eq!(unfiltered.len(), 4);
+    }
+
+    #[test]
+    fn create_nodes_ids_returns_exactly_the_created_ids_and_each_resolves_to_a_real_node() {
+        let storage = temp_storage();
+
+        let ids = storage
+            .create_nodes_ids(vec![
+                ("person".to_string(), HashMap::new()),
+                ("company".to_string(), HashMap::new()),
+            ])
+            .unwrap();
+
+        assert_eq!(ids.len(), 2);
+        let ids: Vec<String> = ids.into_iter().map(|r| r.unwrap()).collect();
+        for id in &ids {
+            assert!(storage.node_exists(id).unwrap());
+        }
+
+        let mut all_ids: Vec<String> = storage.get_all_nodes().unwrap().into_iter().map(|n| n.id).collect();
+        all_ids.sort();
+        let mut ids = ids;
+        ids.sort();
+        assert_eq!(all_ids, ids);
+    }
+
+    #[test]
+    fn create_edges_reports_per_edge_failures_for_missing_endpoints() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let missing = uuid::Uuid::new_v4().to_string();
+
+        let results = storage
+            .create_edges(vec![
+                ("knows".to_string(), a.id.clone(), b.id.clone(), HashMap::new()),
+                ("knows".to_string(), a.id.clone(), missing.clone(), HashMap::new()),
+                ("knows".to_string(), missing.clone(), b.id.clone(), HashMap::new()),
+            ])
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(matches!(&results[1], Err(GraphError::NodeNotFound(id)) if *id == missing));
+        assert!(matches!(&results[2], Err(GraphError::NodeNotFound(id)) if *id == missing));
+
+        assert_eq!(storage.get_all_edges().unwrap().len(), 1);
+        assert_eq!(storage.count_out_edges(&a.id, Some("knows")).unwrap(), 1);
+    }
+
+    #[test]
+    fn rename_node_label_only_touches_matching_nodes() {
+        let storage = temp_storage();
+        let person1 = storage.create_node("person", HashMap::new()).unwrap();
+        let person2 = storage.create_node("person", HashMap::new()).unwrap();
+        let company = storage.create_node("company", HashMap::new()).unwrap();
+
+        let renamed = storage.rename_node_label("person", "user").unwrap();
+        assert_eq!(renamed, 2);
+
+        let by_label = |label: &str| -> Vec<String> {
+            storage
+                .get_all_nodes()
+                .unwrap()
+                .into_iter()
+                .filter(|n| n.label == label)
+                .map(|n| n.id)
+                .collect()
+        };
+        let mut users = by_label("user");
+        users.sort();
+        let mut expected = vec![person1.id, person2.id];
+        expected.sort();
+        assert_eq!(users, expected);
+        assert!(by_label("person").is_empty());
+        assert_eq!(by_label("company"), vec![company.id]);
+    }
+
+    #[test]
+    fn rename_edge_label_rewrites_edge_and_adjacency_records() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+
+        let renamed = storage.rename_edge_label("knows", "befriended").unwrap();
+        assert_eq!(renamed, 1);
+
+        let all = storage.get_all_edges().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].label, "befriended");
+        assert_eq!(
+            storage.count_out_edges(&a.id, Some("befriended")).unwrap(),
+            1
+        );
+        assert_eq!(storage.count_out_edges(&a.id, Some("knows")).unwrap(), 0);
+    }
+
+    #[test]
+    fn create_node_with_id_error_if_exists_rejects_a_collision() {
+        let storage = temp_storage();
+        let mut props = HashMap::new();
+        props.insert("name".to_string(), Value::String("Ada".to_string()));
+        let node = storage
+            .create_node_with_id("fixed-id", "person", props, CreateMode::ErrorIfExists)
+            .unwrap();
+
+        let result = storage.create_node_with_id(
+            &node.id,
+            "person",
+            HashMap::new(),
+            CreateMode::ErrorIfExists,
+        );
+        assert!(matches!(result, Err(GraphError::New(_))));
+        assert_eq!(
+            storage.get_node(&node.id).unwrap().properties.get("name"),
+            Some(&Value::String("Ada".to_string()))
+        );
+    }
+
+    #[test]
+    fn create_node_with_id_replace_discards_old_properties() {
+        let storage = temp_storage();
+        let mut old_props = HashMap::new();
+        old_props.insert("name".to_string(), Value::String("Ada".to_string()));
+        old_props.insert("age".to_string(), Value::Integer(30));
+        let node = storage
+            .create_node_with_id("fixed-id", "person", old_props, CreateMode::ErrorIfExists)
+            .unwrap();
+
+        let mut new_props = HashMap::new();
+        new_props.insert("name".to_string(), Value::String("Grace".to_string()));
+        let replaced = storage
+            .create_node_with_id(&node.id, "person", new_props, CreateMode::Replace)
+            .unwrap();
+
+        assert_eq!(
+            replaced.properties.get("name"),
+            Some(&Value::String("Grace".to_string()))
+        );
+        assert_eq!(replaced.properties.get("age"), None);
+    }
+
+    #[test]
+    fn create_node_with_id_merge_unions_properties_with_new_values_winning() {
+        let storage = temp_storage();
+        let mut old_props = HashMap::new();
+        old_props.insert("name".to_string(), Value::String("Ada".to_string()));
+        old_props.insert("age".to_string(), Value::Integer(30));
+        let node = storage
+            .create_node_with_id("fixed-id", "person", old_props, CreateMode::ErrorIfExists)
+            .unwrap();
+
+        let mut new_props = HashMap::new();
+        new_props.insert("age".to_string(), Value::Integer(31));
+        new_props.insert("city".to_string(), Value::String("London".to_string()));
+        let merged = storage
+            .create_node_with_id(&node.id, "person", new_props, CreateMode::Merge)
+            .unwrap();
+
+        assert_eq!(
+            merged.properties.get("name"),
+            Some(&Value::String("Ada".to_string())),
+            "merge keeps properties the new map didn't touch"
+        );
+        assert_eq!(merged.properties.get("age"), Some(&Value::Integer(31)));
+        assert_eq!(
+            merged.properties.get("city"),
+            Some(&Value::String("London".to_string()))
+        );
+    }
+
+    #[test]
+    fn edge_property_index_returns_only_matches() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        storage.create_edge_index("knows", "since").unwrap();
+
+        let mut props_2020 = HashMap::new();
+        props_2020.insert("since".to_string(), Value::Integer(2020));
+        let e1 = storage.create_edge("knows", &a.id, &b.id, props_2020).unwrap();
+
+        let mut props_2021 = HashMap::new();
+        props_2021.insert("since".to_string(), Value::Integer(2021));
+        storage.create_edge("knows", &a.id, &b.id, props_2021).unwrap();
+
+        let matches = storage
+            .get_edges_by_property("knows", "since", &Value::Integer(2020))
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, e1.id);
+    }
+
+    #[test]
+    fn edge_exists_and_node_exists_each_only_match_their_own_kind() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let edge = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+
+        assert!(storage.node_exists(&a.id).unwrap());
+        assert!(!storage.edge_exists(&a.id).unwrap());
+
+        assert!(storage.edge_exists(&edge.id).unwrap());
+        assert!(!storage.node_exists(&edge.id).unwrap());
+    }
+
+    #[test]
+    fn drop_node_edges_isolates_the_node_but_leaves_it_in_place() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let c = storage.create_node("person", HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        storage.create_edge("knows", &c.id, &a.id, HashMap::new()).unwrap();
+        storage.create_edge("knows", &b.id, &c.id, HashMap::new()).unwrap();
+
+        let removed = storage.drop_node_edges(&a.id).unwrap();
+
+        assert_eq!(removed, 2);
+        assert!(storage.node_exists(&a.id).unwrap());
+        assert_eq!(storage.count_out_edges(&a.id, None).unwrap(), 0);
+        assert_eq!(storage.count_in_edges(&a.id, None).unwrap(), 0);
+        // The edge between the two other nodes is untouched.
+        assert_eq!(storage.count_out_edges(&b.id, None).unwrap(), 1);
+    }
+
+    #[test]
+    fn drop_node_removes_its_out_edges_from_get_all_edges() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let c = storage.create_node("person", HashMap::new()).unwrap();
+        let ab = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        let ac = storage.create_edge("knows", &a.id, &c.id, HashMap::new()).unwrap();
+
+        storage.drop_node(&a.id).unwrap();
+
+        let remaining = storage.get_all_edges().unwrap();
+        assert!(!remaining.iter().any(|edge| edge.id == ab.id));
+        assert!(!remaining.iter().any(|edge| edge.id == ac.id));
+    }
+
+    #[test]
+    fn drop_nodes_and_drop_edges_skip_unknown_ids_but_remove_the_rest() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let c = storage.create_node("person", HashMap::new()).unwrap();
+        let ab = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        let bc = storage.create_edge("knows", &b.id, &c.id, HashMap::new()).unwrap();
+
+        let edges_removed = storage.drop_edges(&[ab.id.as_str(), "missing-edge"]).unwrap();
+        assert_eq!(edges_removed, 1);
+        assert!(!storage.edge_exists(&ab.id).unwrap());
+        assert!(storage.edge_exists(&bc.id).unwrap());
+
+        let nodes_removed = storage.drop_nodes(&[a.id.as_str(), "missing-node"]).unwrap();
+        assert_eq!(nodes_removed, 1);
+        assert!(!storage.node_exists(&a.id).unwrap());
+        assert!(storage.node_exists(&b.id).unwrap());
+
+        // Dropping `b` cascades the surviving `bc` edge too.
+        let more_removed = storage.drop_nodes(&[b.id.as_str()]).unwrap();
+        assert_eq!(more_removed, 1);
+        assert!(!storage.edge_exists(&bc.id).unwrap());
+        assert!(storage.node_exists(&c.id).unwrap());
+
+        let report = storage.verify_integrity().unwrap();
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn create_node_stamps_created_at_near_now_unless_disabled() {
+        let storage = temp_storage();
+        let before = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+        let created_at = match node.properties.get("created_at") {
+            Some(Value::Integer(millis)) => *millis,
+            other => panic!("expected an Integer created_at, got {other:?}"),
+        };
+        assert!(created_at >= before && created_at - before < 5_000);
+
+        let mut no_stamp = temp_storage();
+        no_stamp.config.auto_created_at = false;
+        let node = no_stamp.create_node("person", HashMap::new()).unwrap();
+        assert_eq!(node.properties.get("created_at"), None);
+    }
+
+    #[test]
+    fn create_edges_stamps_created_at_on_every_bulk_created_edge() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        let results = storage
+            .create_edges(vec![("knows".to_string(), a.id.clone(), b.id.clone(), HashMap::new())])
+            .unwrap();
+        let edge = results[0].as_ref().unwrap();
+        match edge.properties.get("created_at") {
+            Some(Value::Integer(_)) => {}
+            other => panic!("expected an Integer created_at, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn get_out_edges_and_get_in_edges_return_every_label_when_unfiltered() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let knows = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        let follows = storage.create_edge("follows", &a.id, &b.id, HashMap::new()).unwrap();
+        let blocks = storage.create_edge("blocks", &a.id, &b.id, HashMap::new()).unwrap();
+
+        let out_edges = storage.get_out_edges(&a.id, None).unwrap();
+        let mut out_ids: Vec<_> = out_edges.iter().map(|e| e.id.clone()).collect();
+        out_ids.sort();
+        let mut expected_ids = vec![knows.id.clone(), follows.id.clone(), blocks.id.clone()];
+        expected_ids.sort();
+        assert_eq!(out_ids, expected_ids);
+
+        let in_edges = storage.get_in_edges(&b.id, None).unwrap();
+        assert_eq!(in_edges.len(), 3);
+
+        let filtered = storage.get_out_edges(&a.id, Some("follows")).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, follows.id);
+    }
+
+    #[test]
+    fn degree_counters_match_a_full_scan_after_a_series_of_creates_and_drops() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+        let c = storage.create_node("person", HashMap::new()).unwrap();
+
+        let e1 = storage.create_edge("knows", &a.id, &b.id, HashMap::new()).unwrap();
+        storage.create_edge("knows", &a.id, &c.id, HashMap::new()).unwrap();
+        storage.create_edge("follows", &a.id, &b.id, HashMap::new()).unwrap();
+        storage.drop_edge(&e1.id).unwrap();
+
+        let scan_out = |label: Option<&str>| {
+            collect_adjacency(&storage, &format!("o:{}:", a.id), label)
+                .unwrap()
+                .len()
+        };
+        let scan_in = |label: Option<&str>| {
+            collect_adjacency(&storage, &format!("i:{}:", b.id), label)
+                .unwrap()
+                .len()
+        };
+
+        assert_eq!(storage.count_out_edges(&a.id, None).unwrap(), scan_out(None));
+        assert_eq!(storage.count_out_edges(&a.id, None).unwrap(), 2);
+        assert_eq!(
+            storage.count_out_edges(&a.id, Some("knows")).unwrap(),
+            scan_out(Some("knows"))
+        );
+        assert_eq!(storage.count_out_edges(&a.id, Some("knows")).unwrap(), 1);
+        assert_eq!(storage.count_in_edges(&b.id, None).unwrap(), scan_in(None));
+        assert_eq!(storage.count_in_edges(&b.id, None).unwrap(), 1);
+    }
+
+    #[test]
+    fn get_nodes_by_ids_preserves_order_and_duplicates_and_errors_on_a_missing_id() {
+        let storage = temp_storage();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        let found = storage
+            .get_nodes_by_ids(&[a.id.clone(), b.id.clone(), a.id.clone()])
+            .unwrap();
+        assert_eq!(found.iter().map(|n| n.id.clone()).collect::<Vec<_>>(), vec![a.id.clone(), b.id.clone(), a.id.clone()]);
+
+        let err = storage.get_nodes_by_ids(&[a.id.clone(), "missing".to_string()]);
+        assert!(matches!(err, Err(GraphError::NodeNotFound(id)) if id == "missing"));
+    }
+
+    #[test]
+    fn get_node_ids_by_label_matches_get_nodes_by_label_and_never_reads_node_values() {
+        let storage = temp_storage();
+        let user_a = storage.create_node("user", HashMap::new()).unwrap();
+        let user_b = storage.create_node("user", HashMap::new()).unwrap();
+        storage.create_node("company", HashMap::new()).unwrap();
+
+        // Corrupt the raw node record for one of the two "user" nodes. If
+        // `get_node_ids_by_label` ever deserialized a node value instead of
+        // parsing ids straight out of the `nl:` index keys, this would make
+        // it error instead of returning a clean id list.
+        storage
+            .db
+            .put_cf(storage.cf_nodes(), format!("n:{}", user_a.id), b"not a valid node")
+            .unwrap();
+
+        let mut ids = storage.get_node_ids_by_label("user").unwrap();
+        ids.sort();
+        let mut expected = vec![user_a.id.clone(), user_b.id.clone()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // Confirm the corruption really would break a value-reading path,
+        // so the assertion above is actually exercising the no-read claim.
+        assert!(storage.get_nodes_by_label("user").is_err());
+    }
+
+    #[test]
+    fn get_node_returns_a_storage_error_instead_of_panicking_on_a_corrupt_record() {
+        let storage = temp_storage();
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+
+        storage
+            .db
+            .put_cf(storage.cf_nodes(), format!("n:{}", node.id), b"not a valid node")
+            .unwrap();
+
+        match storage.get_node(&node.id) {
+            Err(GraphError::StorageError(msg)) => assert!(msg.contains(&node.id)),
+            other => panic!("expected StorageError naming the corrupt node's id, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn coerce_property_converts_string_encoded_numbers_to_integers() {
+        let storage = temp_storage();
+        let mut props = HashMap::new();
+        props.insert("age".to_string(), Value::String("22".to_string()));
+        let node = storage.create_node("user", props).unwrap();
+        storage.create_node("company", HashMap::new()).unwrap();
+
+        let changed = storage.coerce_property("user", "age", DataType::Integer).unwrap();
+
+        assert_eq!(changed, 1);
+        let reloaded = storage.get_node(&node.id).unwrap();
+        assert_eq!(reloaded.properties.get("age"), Some(&Value::Integer(22)));
+    }
+
+    #[test]
+    fn coerce_property_leaves_non_numeric_strings_untouched() {
+        let storage = temp_storage();
+        let mut props = HashMap::new();
+        props.insert("age".to_string(), Value::String("not a number".to_string()));
+        let node = storage.create_node("user", props).unwrap();
+
+        let changed = storage.coerce_property("user", "age", DataType::Integer).unwrap();
+
+        assert_eq!(changed, 0);
+        let reloaded = storage.get_node(&node.id).unwrap();
+        assert_eq!(
+            reloaded.properties.get("age"),
+            Some(&Value::String("not a number".to_string()))
+        );
+    }
+
+    #[test]
+    fn create_edge_ensure_nodes_creates_missing_endpoints_with_the_given_labels() {
+        let storage = temp_storage();
+        let from_id = uuid::Uuid::new_v4().to_string();
+        let to_id = uuid::Uuid::new_v4().to_string();
+
+        let edge = storage
+            .create_edge_ensure_nodes("knows", "person", &from_id, "person", &to_id, HashMap::new())
+            .unwrap();
+
+        assert_eq!(edge.from_node, from_id);
+        assert_eq!(edge.to_node, to_id);
+
+        let from_node = storage.get_node(&from_id).unwrap();
+        let to_node = storage.get_node(&to_id).unwrap();
+        assert_eq!(from_node.label, "person");
+        assert_eq!(to_node.label, "person");
+
+        assert_eq!(storage.get_out_edges(&from_id, Some("knows")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn create_edge_ensure_nodes_leaves_an_existing_endpoint_untouched() {
+        let storage = temp_storage();
+        let mut props = HashMap::new();
+        props.insert("name".to_string(), Value::String("alice".to_string()));
+        let existing = storage.create_node("person", props).unwrap();
+        let to_id = uuid::Uuid::new_v4().to_string();
+
+        storage
+            .create_edge_ensure_nodes("knows", "person", &existing.id, "person", &to_id, HashMap::new())
+            .unwrap();
+
+        let unchanged = storage.get_node(&existing.id).unwrap();
+        assert_eq!(unchanged.get_str("name"), Some("alice"));
+    }
+
+    #[test]
+    fn create_edge_with_a_missing_from_node_names_the_from_id() {
+        let storage = temp_storage();
+        let to = storage.create_node("person", HashMap::new()).unwrap();
+        let missing_from = uuid::Uuid::new_v4().to_string();
+
+        match storage.create_edge("knows", &missing_from, &to.id, HashMap::new()) {
+            Err(GraphError::Validation(msg)) => {
+                assert!(msg.contains(&missing_from));
+                assert!(!msg.contains(&to.id));
+            }
+            other => panic!("expected Validation naming the missing from id, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn create_edge_with_a_missing_to_node_names_the_to_id() {
+        let storage = temp_storage();
+        let from = storage.create_node("person", HashMap::new()).unwrap();
+        let missing_to = uuid::Uuid::new_v4().to_string();
+
+        match storage.create_edge("knows", &from.id, &missing_to, HashMap::new()) {
+            Err(GraphError::Validation(msg)) => {
+                assert!(msg.contains(&missing_to));
+                assert!(!msg.contains(&from.id));
+            }
+            other => panic!("expected Validation naming the missing to id, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn create_edge_with_both_nodes_missing_names_both_ids() {
+        let storage = temp_storage();
+        let missing_from = uuid::Uuid::new_v4().to_string();
+        let missing_to = uuid::Uuid::new_v4().to_string();
+
+        match storage.create_edge("knows", &missing_from, &missing_to, HashMap::new()) {
+            Err(GraphError::Validation(msg)) => {
+                assert!(msg.contains(&missing_from));
+                assert!(msg.contains(&missing_to));
+            }
+            other => panic!("expected Validation naming both missing ids, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn self_loops_are_created_by_default() {
+        let storage = temp_storage();
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+
+        let edge = storage
+            .create_edge("knows", &node.id, &node.id, HashMap::new())
+            .unwrap();
+        assert_eq!(edge.from_node, node.id);
+        assert_eq!(edge.to_node, node.id);
+    }
+
+    #[test]
+    fn self_loops_are_rejected_when_disabled() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = crate::helix_engine::storage_core::HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                allow_self_loops: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let node = storage.create_node("person", HashMap::new()).unwrap();
+
+        match storage.create_edge("knows", &node.id, &node.id, HashMap::new()) {
+            Err(GraphError::Validation(msg)) => assert!(msg.contains(&node.id)),
+            other => panic!("expected a Validation error rejecting the self-loop, got {other:?}"),
+        }
+
+        let other = storage.create_node("person", HashMap::new()).unwrap();
+        storage
+            .create_edge("knows", &node.id, &other.id, HashMap::new())
+            .unwrap();
+    }
+
+    #[test]
+    fn create_edges_and_create_edge_ensure_nodes_also_reject_self_loops_when_disabled() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = crate::helix_engine::storage_core::HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                allow_self_loops: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        let results = storage
+            .create_edges(vec![
+                ("knows".to_string(), a.id.clone(), a.id.clone(), HashMap::new()),
+                ("knows".to_string(), a.id.clone(), b.id.clone(), HashMap::new()),
+            ])
+            .unwrap();
+        match &results[0] {
+            Err(GraphError::Validation(msg)) => assert!(msg.contains(&a.id)),
+            other => panic!("expected create_edges to reject the self-loop, got {other:?}"),
+        }
+        assert!(results[1].is_ok(), "the non-self-loop edge should still succeed: {:?}", results[1]);
+
+        match storage.create_edge_ensure_nodes("knows", "person", &a.id, "person", &a.id, HashMap::new()) {
+            Err(GraphError::Validation(msg)) => assert!(msg.contains(&a.id)),
+            other => panic!("expected create_edge_ensure_nodes to reject the self-loop, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn create_edges_also_rejects_undeclared_labels() {
+        let dir = std::env::temp_dir().join(format!("helix-test-{}", uuid::Uuid::new_v4()));
+        let storage = crate::helix_engine::storage_core::HelixGraphStorage::open_with_config(
+            dir.to_str().unwrap(),
+            crate::helix_engine::storage_core::SerializationFormat::Bincode,
+            crate::helix_engine::storage_core::StorageConfig {
+                allowed_edge_labels: Some(HashSet::from(["knows".to_string()])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let a = storage.create_node("person", HashMap::new()).unwrap();
+        let b = storage.create_node("person", HashMap::new()).unwrap();
+
+        let results = storage
+            .create_edges(vec![("hates".to_string(), a.id.clone(), b.id.clone(), HashMap::new())])
+            .unwrap();
+        match &results[0] {
+            Err(GraphError::StorageError(msg)) => assert!(msg.contains("hates")),
+            other => panic!("expected create_edges to reject the undeclared label, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// Errors surfaced by the storage and graph engine layers.
+#[derive(Debug)]
+pub enum GraphError {
+    NodeNotFound(String),
+    EdgeNotFound(String),
+    StorageError(String),
+    New(String),
+    Validation(String),
+    StorageConnectionError(String),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::NodeNotFound(id) => write!(f, "node not found: {id}"),
+            GraphError::EdgeNotFound(id) => write!(f, "edge not found: {id}"),
+            GraphError::StorageError(msg) => write!(f, "storage error: {msg}"),
+            GraphError::New(msg) => write!(f, "{msg}"),
+            GraphError::Validation(msg) => write!(f, "validation error: {msg}"),
+            GraphError::StorageConnectionError(msg) => write!(f, "failed to open storage: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+impl From<rocksdb::Error> for GraphError {
+    fn from(err: rocksdb::Error) -> Self {
+        GraphError::StorageError(err.to_string())
+    }
+}
+
+impl From<bincode::Error> for GraphError {
+    fn from(err: bincode::Error) -> Self {
+        GraphError::StorageError(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for GraphError {
+    fn from(err: serde_json::Error) -> Self {
+        GraphError::StorageError(err.to_string())
+    }
+}
@@ -0,0 +1,110 @@
+//! Async (tokio) alternative to the blocking accept-and-dispatch model in
+//! [`crate::helix_gateway::worker`].
+//!
+//! The blocking model dedicates one OS thread per connection for as long as
+//! that connection is open, which caps in-flight connections at however many
+//! threads the pool was sized for and wastes a thread on a client that's
+//! just sitting there slowly trickling in a request. [`AsyncConnectionHandler`]
+//! accepts connections on a single async task instead, then hands each one
+//! to tokio's blocking thread pool (which scales far past a fixed
+//! thread-per-connection pool) so it can run the *exact same*
+//! [`handle_connection`] loop — same `HelixRouter`, same `Request`/`Response`
+//! parsing, same read-timeout handling — unchanged.
+//!
+//! Gated behind the `async-gateway` feature: it's an alternative front door,
+//! not a replacement for [`crate::helix_gateway::listener::ConnectionHandler`].
+#![cfg(feature = "async-gateway")]
+
+use crate::helix_gateway::router::HelixRouter;
+use crate::helix_gateway::worker::{handle_connection, GatewayOpts};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Tokio-backed counterpart to [`crate::helix_gateway::listener::ConnectionHandler`].
+pub struct AsyncConnectionHandler {
+    listener: TcpListener,
+}
+
+impl AsyncConnectionHandler {
+    pub async fn bind(addr: &str) -> io::Result<Self> {
+        Ok(AsyncConnectionHandler {
+            listener: TcpListener::bind(addr).await?,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever, spawning each onto tokio's blocking
+    /// thread pool via [`tokio::task::spawn_blocking`] so [`handle_connection`]
+    /// can run its ordinary synchronous read/dispatch/write loop against the
+    /// std `TcpStream` tokio hands back from [`tokio::net::TcpStream::into_std`].
+    /// Never returns on success — only on an error accepting a new connection.
+    pub async fn serve(&self, router: Arc<HelixRouter>, opts: GatewayOpts) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept().await?;
+            let stream = stream.into_std()?;
+            stream.set_nonblocking(false)?;
+            let router = router.clone();
+            tokio::task::spawn_blocking(move || handle_connection(stream, &router, &opts));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{Method, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn async_handler_serves_concurrent_requests_past_a_small_thread_pool_size() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let hits_in_handler = hits.clone();
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/slow",
+            Arc::new(move |_input| {
+                // Simulates a slow handler: long enough that a pool sized for
+                // a handful of threads couldn't serve this many concurrently
+                // within the test's timeout.
+                std::thread::sleep(Duration::from_millis(50));
+                hits_in_handler.fetch_add(1, Ordering::SeqCst);
+                Ok(Response::ok(b"ok".to_vec()))
+            }),
+        );
+        let router = Arc::new(router);
+
+        let handler = AsyncConnectionHandler::bind("127.0.0.1:0").await.unwrap();
+        let addr = handler.local_addr().unwrap();
+        let opts = GatewayOpts {
+            read_timeout: Duration::from_millis(200),
+        };
+        tokio::spawn(async move {
+            let _ = handler.serve(router, opts).await;
+        });
+
+        const CLIENTS: usize = 64;
+        let mut tasks = Vec::with_capacity(CLIENTS);
+        for _ in 0..CLIENTS {
+            tasks.push(tokio::spawn(async move {
+                let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+                stream.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+                let mut buf = Vec::new();
+                stream.read_to_end(&mut buf).await.unwrap();
+                String::from_utf8_lossy(&buf).contains("ok")
+            }));
+        }
+
+        for task in tasks {
+            assert!(task.await.unwrap());
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), CLIENTS);
+    }
+}
@@ -0,0 +1,78 @@
+use socket2::{Domain, Socket, Type};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+
+/// Tunables for the gateway's listening socket.
+#[derive(Debug, Clone, Copy)]
+pub struct ListenerConfig {
+    /// Pending-connection queue size passed to `listen(2)`. Defaults to
+    /// 1024, well above the std library's OS-default backlog, so a burst of
+    /// connections under load doesn't get refused before `accept` catches up.
+    pub backlog: i32,
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        ListenerConfig { backlog: 1024 }
+    }
+}
+
+/// Owns the gateway's listening socket.
+///
+/// Binds through `socket2` instead of [`TcpListener::bind`] so `SO_REUSEADDR`
+/// can be set before binding: without it, restarting the gateway on the same
+/// port can fail with "address in use" while the previous socket's
+/// connections are still draining through `TIME_WAIT`.
+pub struct ConnectionHandler {
+    pub listener: TcpListener,
+}
+
+impl ConnectionHandler {
+    pub fn new(addr: &str, config: ListenerConfig) -> io::Result<Self> {
+        let addr: SocketAddr = addr
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let domain = if addr.is_ipv6() { Domain::IPV6 } else { Domain::IPV4 };
+
+        let socket = Socket::new(domain, Type::STREAM, None)?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&addr.into())?;
+        socket.listen(config.backlog)?;
+
+        Ok(ConnectionHandler {
+            listener: socket.into(),
+        })
+    }
+
+    pub fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        self.listener.accept()
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_the_same_port_after_drop_succeeds() {
+        let first = ConnectionHandler::new("127.0.0.1:0", ListenerConfig::default()).unwrap();
+        let addr = first.local_addr().unwrap();
+        drop(first);
+
+        let second = ConnectionHandler::new(&addr.to_string(), ListenerConfig::default());
+        assert!(
+            second.is_ok(),
+            "rebinding the just-freed port should not fail with address-in-use: {:?}",
+            second.err()
+        );
+    }
+
+    #[test]
+    fn listener_config_defaults_to_a_large_backlog() {
+        assert_eq!(ListenerConfig::default().backlog, 1024);
+    }
+}
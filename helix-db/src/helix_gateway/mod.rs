@@ -0,0 +1,10 @@
+#[cfg(feature = "async-gateway")]
+pub mod async_worker;
+pub mod listener;
+pub mod router;
+pub mod worker;
+
+#[cfg(feature = "async-gateway")]
+pub use async_worker::AsyncConnectionHandler;
+pub use listener::{ConnectionHandler, ListenerConfig};
+pub use router::{HelixRouter, RouterError};
@@ -0,0 +1,3 @@
+pub mod router;
+
+pub use router::{Handler, HandlerInput, HandlerSubmission, HelixRouter, QueryParams, RouterError};
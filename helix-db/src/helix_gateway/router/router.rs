@@ -0,0 +1,579 @@
+use crate::protocol::{Method, Request, Response};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub enum RouterError {
+    NotFound,
+    HandlerError(String),
+}
+
+impl fmt::Display for RouterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouterError::NotFound => write!(f, "no route matched"),
+            RouterError::HandlerError(msg) => write!(f, "handler error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RouterError {}
+
+/// Lets a handler body call a storage method with `?` instead of a manual
+/// `.map_err(|e| RouterError::HandlerError(e.to_string()))` on every call.
+impl From<crate::helix_engine::types::GraphError> for RouterError {
+    fn from(err: crate::helix_engine::types::GraphError) -> Self {
+        RouterError::HandlerError(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for RouterError {
+    fn from(err: std::io::Error) -> Self {
+        RouterError::HandlerError(err.to_string())
+    }
+}
+
+/// Path and query-string parameters made available to a handler.
+///
+/// Populated from `:name` segments in a pattern route (e.g. `/friends/:id`)
+/// and from `?key=value` pairs on the request path; path parameters are
+/// merged in after query parameters, so a query string can't shadow a path
+/// segment with the same name.
+pub type QueryParams = HashMap<String, String>;
+
+/// Everything a registered handler needs to produce a response.
+///
+/// `match_suffix` is only set for prefix routes: it's the portion of the
+/// path after the matched prefix, e.g. registering `/graph/` and receiving
+/// `/graph/nodes` gives a suffix of `nodes`.
+pub struct HandlerInput {
+    pub request: Request,
+    pub match_suffix: Option<String>,
+    pub params: QueryParams,
+    pub accept: Option<String>,
+    /// Correlation id for this request, echoed back on the response as
+    /// `X-Request-Id` by [`HelixRouter::dispatch`]. Taken from the client's
+    /// own `X-Request-Id` header when present, otherwise generated fresh —
+    /// either way a handler can log it alongside its own output to tie
+    /// server-side logs back to the request that caused them.
+    pub request_id: String,
+}
+
+impl HandlerInput {
+    pub fn param_str(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    pub fn param_int(&self, key: &str) -> Option<i64> {
+        self.param_str(key).and_then(|v| v.parse().ok())
+    }
+
+    pub fn accept(&self) -> Option<&str> {
+        self.accept.as_deref()
+    }
+
+    /// Decodes the request body as `application/x-www-form-urlencoded` via
+    /// [`Request::form_body`], first checking the `Content-Type` header
+    /// actually says so — a JSON or empty body silently parsed as form data
+    /// would otherwise produce a confusing, wrong-looking map instead of an
+    /// error pointing at the real problem.
+    pub fn form_body(&self) -> Result<QueryParams, RouterError> {
+        let content_type = self.request.headers.get("Content-Type").map(String::as_str);
+        match content_type {
+            Some(ct) if ct.starts_with("application/x-www-form-urlencoded") => {
+                Ok(self.request.form_body())
+            }
+            other => Err(RouterError::HandlerError(format!(
+                "expected Content-Type: application/x-www-form-urlencoded, got {other:?}"
+            ))),
+        }
+    }
+}
+
+pub type Handler = Arc<dyn Fn(&HandlerInput) -> Result<Response, RouterError> + Send + Sync>;
+
+/// One handler's route registration, carrying its own `method` rather than
+/// a bare `(path, handler)` pair. Lets a route collector (e.g. one built
+/// from a `#[handler]`-style macro) register a batch of handlers via
+/// [`HelixRouter::register_all`] without assuming every one of them is GET.
+pub struct HandlerSubmission {
+    pub method: Method,
+    pub path: String,
+    pub handler: Handler,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    pattern
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.strip_prefix(':') {
+            Some(name) => Segment::Param(name.to_string()),
+            None => Segment::Literal(s.to_string()),
+        })
+        .collect()
+}
+
+fn match_pattern(segments: &[Segment], path: &str) -> Option<QueryParams> {
+    let path_parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if path_parts.len() != segments.len() {
+        return None;
+    }
+    let mut params = QueryParams::new();
+    for (segment, part) in segments.iter().zip(path_parts.iter()) {
+        match segment {
+            Segment::Literal(lit) if lit == part => {}
+            Segment::Literal(_) => return None,
+            Segment::Param(name) => {
+                params.insert(name.clone(), part.to_string());
+            }
+        }
+    }
+    Some(params)
+}
+
+/// Dispatches incoming requests to registered handlers by method + path.
+///
+/// Exact routes are checked first, then `:param` pattern routes, then prefix
+/// routes (registered with [`HelixRouter::add_prefix_route`]) — the longest
+/// matching prefix wins among those.
+#[derive(Default)]
+pub struct HelixRouter {
+    routes: HashMap<(Method, String), Handler>,
+    pattern_routes: Vec<(Method, Vec<Segment>, Handler)>,
+    prefix_routes: Vec<(Method, String, Handler)>,
+}
+
+impl HelixRouter {
+    pub fn new() -> Self {
+        HelixRouter {
+            routes: HashMap::new(),
+            pattern_routes: Vec::new(),
+            prefix_routes: Vec::new(),
+        }
+    }
+
+    pub fn add_route(&mut self, method: Method, path: &str, handler: Handler) {
+        self.routes.insert((method, path.to_string()), handler);
+    }
+
+    /// Registers every [`HandlerSubmission`] under its own declared
+    /// `method`, instead of a collector that only ever calls `add_route`
+    /// with a single hard-coded method regardless of what each submission
+    /// actually wants.
+    pub fn register_all(&mut self, submissions: impl IntoIterator<Item = HandlerSubmission>) {
+        for submission in submissions {
+            self.add_route(submission.method, &submission.path, submission.handler);
+        }
+    }
+
+    /// Registers a handler for a path pattern containing `:name` segments,
+    /// e.g. `/friends/:id`.
+    pub fn add_pattern_route(&mut self, method: Method, pattern: &str, handler: Handler) {
+        self.pattern_routes
+            .push((method, parse_pattern(pattern), handler));
+    }
+
+    /// Registers a handler that catches any path starting with `prefix`.
+    pub fn add_prefix_route(&mut self, method: Method, prefix: &str, handler: Handler) {
+        self.prefix_routes
+            .push((method, prefix.to_string(), handler));
+    }
+
+    pub fn dispatch(&self, request: Request) -> Result<Response, RouterError> {
+        let route_path = request.path.clone();
+        let query_params = request.query.clone();
+        let accept = request.headers.get("Accept").cloned();
+        let request_id = request
+            .headers
+            .get("X-Request-Id")
+            .cloned()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        if let Some(handler) = self.routes.get(&(request.method, route_path.clone())) {
+            let input = HandlerInput {
+                request,
+                match_suffix: None,
+                params: query_params,
+                accept,
+                request_id: request_id.clone(),
+            };
+            return handler(&input).map(|response| echo_request_id(response, request_id));
+        }
+
+        for (method, segments, handler) in &self.pattern_routes {
+            if *method != request.method {
+                continue;
+            }
+            if let Some(path_params) = match_pattern(segments, &route_path) {
+                let mut params = query_params.clone();
+                params.extend(path_params);
+                let input = HandlerInput {
+                    request,
+                    match_suffix: None,
+                    params,
+                    accept,
+                    request_id: request_id.clone(),
+                };
+                return handler(&input).map(|response| echo_request_id(response, request_id));
+            }
+        }
+
+        let best = self
+            .prefix_routes
+            .iter()
+            .filter(|(method, prefix, _)| *method == request.method && route_path.starts_with(prefix.as_str()))
+            .max_by_key(|(_, prefix, _)| prefix.len());
+
+        if let Some((_, prefix, handler)) = best {
+            let suffix = route_path[prefix.len()..].to_string();
+            let input = HandlerInput {
+                request,
+                match_suffix: Some(suffix),
+                params: query_params,
+                accept,
+                request_id: request_id.clone(),
+            };
+            return handler(&input).map(|response| echo_request_id(response, request_id));
+        }
+
+        Err(RouterError::NotFound)
+    }
+}
+
+/// Sets `X-Request-Id` on `response` to `request_id`, overwriting any value
+/// a handler may have already set.
+fn echo_request_id(mut response: Response, request_id: String) -> Response {
+    response.headers.insert("X-Request-Id".to_string(), request_id);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok_handler(body: &'static str) -> Handler {
+        Arc::new(move |_input: &HandlerInput| Ok(Response::ok(body.as_bytes().to_vec())))
+    }
+
+    #[test]
+    fn exact_route_takes_precedence_over_prefix() {
+        let mut router = HelixRouter::new();
+        router.add_prefix_route(Method::Get, "/", ok_handler("catch-all"));
+        router.add_route(Method::Get, "/health", ok_handler("healthy"));
+
+        let resp = router.dispatch(Request::new(Method::Get, "/health")).unwrap();
+        assert_eq!(resp.body, b"healthy");
+    }
+
+    #[test]
+    fn prefix_route_catches_namespace_paths() {
+        let mut router = HelixRouter::new();
+        router.add_route(Method::Get, "/health", ok_handler("healthy"));
+        router.add_prefix_route(
+            Method::Get,
+            "/graph/",
+            Arc::new(|input: &HandlerInput| {
+                Ok(Response::ok(
+                    input.match_suffix.clone().unwrap_or_default().into_bytes(),
+                ))
+            }),
+        );
+
+        let resp = router.dispatch(Request::new(Method::Get, "/graph/nodes")).unwrap();
+        assert_eq!(resp.body, b"nodes");
+
+        let resp = router.dispatch(Request::new(Method::Get, "/graph/edges")).unwrap();
+        assert_eq!(resp.body, b"edges");
+
+        let resp = router.dispatch(Request::new(Method::Get, "/health")).unwrap();
+        assert_eq!(resp.body, b"healthy");
+    }
+
+    #[test]
+    fn dispatch_echoes_a_supplied_x_request_id_and_makes_it_available_to_the_handler() {
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(|input: &HandlerInput| Ok(Response::ok(input.request_id.clone().into_bytes()))),
+        );
+
+        let mut request = Request::new(Method::Get, "/health");
+        request
+            .headers
+            .insert("X-Request-Id".to_string(), "client-supplied-id".to_string());
+
+        let resp = router.dispatch(request).unwrap();
+        assert_eq!(resp.body, b"client-supplied-id");
+        assert_eq!(resp.headers.get("X-Request-Id"), Some(&"client-supplied-id".to_string()));
+    }
+
+    #[test]
+    fn dispatch_generates_a_request_id_when_none_is_supplied() {
+        let mut router = HelixRouter::new();
+        router.add_route(Method::Get, "/health", ok_handler("healthy"));
+
+        let resp = router.dispatch(Request::new(Method::Get, "/health")).unwrap();
+        let generated = resp.headers.get("X-Request-Id").expect("X-Request-Id should be set");
+        assert!(!generated.is_empty());
+    }
+
+    #[test]
+    fn pattern_route_exposes_path_param() {
+        let mut router = HelixRouter::new();
+        router.add_pattern_route(
+            Method::Get,
+            "/friends/:id",
+            Arc::new(|input: &HandlerInput| {
+                let id = input.param_str("id").unwrap_or("").to_string();
+                Ok(Response::ok(id.into_bytes()))
+            }),
+        );
+
+        let resp = router
+            .dispatch(Request::new(Method::Get, "/friends/abc"))
+            .unwrap();
+        assert_eq!(resp.body, b"abc");
+    }
+
+    #[test]
+    fn pattern_route_path_param_is_not_shadowed_by_a_same_named_query_param() {
+        let mut router = HelixRouter::new();
+        router.add_pattern_route(
+            Method::Get,
+            "/friends/:id",
+            Arc::new(|input: &HandlerInput| {
+                let id = input.param_str("id").unwrap_or("").to_string();
+                Ok(Response::ok(id.into_bytes()))
+            }),
+        );
+
+        let mut request = Request::new(Method::Get, "/friends/abc");
+        request.query.insert("id".to_string(), "from-query".to_string());
+
+        let resp = router.dispatch(request).unwrap();
+        assert_eq!(resp.body, b"abc");
+    }
+
+    #[test]
+    fn query_string_is_url_decoded_and_does_not_affect_route_matching() {
+        use std::io::BufReader;
+
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/search",
+            Arc::new(|input: &HandlerInput| {
+                let q = input.param_str("q").unwrap_or("").to_string();
+                let limit = input.param_str("limit").unwrap_or("").to_string();
+                Ok(Response::ok(format!("{q}|{limit}").into_bytes()))
+            }),
+        );
+
+        let raw = b"GET /search?q=hello%20world&limit=5 HTTP/1.1\r\n\r\n";
+        let mut reader = BufReader::new(&raw[..]);
+        let request = Request::from_stream(&mut reader).unwrap();
+
+        let resp = router.dispatch(request).unwrap();
+        assert_eq!(resp.body, b"hello world|5");
+    }
+
+    #[test]
+    fn register_all_uses_each_submissions_own_method() {
+        let mut router = HelixRouter::new();
+        router.register_all(vec![
+            HandlerSubmission {
+                method: Method::Delete,
+                path: "/nodes/1".to_string(),
+                handler: ok_handler("deleted"),
+            },
+            HandlerSubmission {
+                method: Method::Put,
+                path: "/nodes/1".to_string(),
+                handler: ok_handler("replaced"),
+            },
+        ]);
+
+        let resp = router.dispatch(Request::new(Method::Delete, "/nodes/1")).unwrap();
+        assert_eq!(resp.body, b"deleted");
+
+        let resp = router.dispatch(Request::new(Method::Put, "/nodes/1")).unwrap();
+        assert_eq!(resp.body, b"replaced");
+
+        let err = router.dispatch(Request::new(Method::Get, "/nodes/1"));
+        assert!(matches!(err, Err(RouterError::NotFound)));
+    }
+
+    #[test]
+    fn form_body_decodes_when_content_type_matches_and_errors_otherwise() {
+        let mut form_request = Request::new(Method::Post, "/nodes");
+        form_request.headers.insert(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        );
+        form_request.body = b"label=person&name=Will".to_vec();
+        let input = HandlerInput {
+            request: form_request,
+            match_suffix: None,
+            params: QueryParams::new(),
+            accept: None,
+            request_id: "test-request".to_string(),
+        };
+        let form = input.form_body().unwrap();
+        assert_eq!(form.get("label"), Some(&"person".to_string()));
+        assert_eq!(form.get("name"), Some(&"Will".to_string()));
+
+        let mut json_request = Request::new(Method::Post, "/nodes");
+        json_request
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        let json_input = HandlerInput {
+            request: json_request,
+            match_suffix: None,
+            params: QueryParams::new(),
+            accept: None,
+            request_id: "test-request".to_string(),
+        };
+        assert!(json_input.form_body().is_err());
+    }
+
+    #[test]
+    fn handler_using_question_mark_on_a_graph_error_surfaces_its_message() {
+        use crate::helix_engine::types::GraphError;
+
+        let mut router = HelixRouter::new();
+        fn fails() -> Result<(), GraphError> {
+            Err(GraphError::NodeNotFound("missing-id".to_string()))
+        }
+
+        router.add_route(
+            Method::Get,
+            "/boom",
+            Arc::new(|_input: &HandlerInput| -> Result<Response, RouterError> {
+                fails()?;
+                Ok(Response::ok(Vec::new()))
+            }),
+        );
+
+        match router.dispatch(Request::new(Method::Get, "/boom")) {
+            Err(RouterError::HandlerError(msg)) => assert!(msg.contains("missing-id")),
+            other => panic!("expected HandlerError, got {other:?}"),
+        }
+    }
+
+    fn temp_storage() -> crate::helix_engine::storage_core::HelixGraphStorage {
+        let dir = std::env::temp_dir().join(format!("helix-router-test-{}", uuid::Uuid::new_v4()));
+        crate::helix_engine::storage_core::HelixGraphStorage::new(dir.to_str().unwrap()).unwrap()
+    }
+
+    /// Exercises a `POST /nodes/bulk?idsonly=true` handler against real
+    /// storage — the closest this repo's test-only router wiring comes to a
+    /// production bulk-create route, since nothing registers routes against
+    /// real storage outside tests. `idsonly=true` should come back as a bare
+    /// JSON array of the new ids rather than the full nodes.
+    #[test]
+    fn nodes_bulk_route_honors_idsonly_and_every_returned_id_resolves_to_a_real_node() {
+        use crate::helix_engine::storage_core::StorageMethods;
+        use crate::protocol::Properties;
+        use std::sync::Arc as StdArc;
+
+        let storage = StdArc::new(temp_storage());
+        let handler_storage = storage.clone();
+
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Post,
+            "/nodes/bulk",
+            Arc::new(move |input: &HandlerInput| -> Result<Response, RouterError> {
+                let specs: Vec<(String, Properties)> = serde_json::from_slice(&input.request.body)
+                    .map_err(|e| RouterError::HandlerError(e.to_string()))?;
+
+                if input.param_str("idsonly") == Some("true") {
+                    let ids: Vec<String> = handler_storage
+                        .create_nodes_ids(specs)?
+                        .into_iter()
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(Response::ok(serde_json::to_vec(&ids).unwrap_or_default()))
+                } else {
+                    let nodes = handler_storage
+                        .create_nodes(specs)?
+                        .into_iter()
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(Response::ok(serde_json::to_vec(&nodes).unwrap_or_default()))
+                }
+            }),
+        );
+
+        let mut request = Request::new(Method::Post, "/nodes/bulk");
+        request.query.insert("idsonly".to_string(), "true".to_string());
+        request.body = serde_json::to_vec(&vec![
+            ("person".to_string(), Properties::new()),
+            ("company".to_string(), Properties::new()),
+        ])
+        .unwrap();
+
+        let resp = router.dispatch(request).unwrap();
+        let ids: Vec<String> = serde_json::from_slice(&resp.body).unwrap();
+
+        assert_eq!(ids.len(), 2);
+        for id in &ids {
+            assert!(storage.node_exists(id).unwrap());
+        }
+    }
+
+    /// Exercises a `PATCH /node/:id` handler against real storage, applying
+    /// a JSON-merge-patch-style body via
+    /// [`crate::helix_engine::storage_core::HelixGraphStorage::patch_node`] —
+    /// setting a new key, overwriting an existing one, and removing another
+    /// in the same request.
+    #[test]
+    fn patch_node_route_sets_overwrites_and_removes_properties_in_one_request() {
+        use crate::helix_engine::storage_core::StorageMethods;
+        use crate::protocol::{Properties, Value};
+        use std::sync::Arc as StdArc;
+
+        let storage = StdArc::new(temp_storage());
+        let mut props = Properties::new();
+        props.insert("name".to_string(), Value::String("Ada".to_string()));
+        props.insert("bio".to_string(), Value::String("mathematician".to_string()));
+        let node = storage.create_node("person", props).unwrap();
+
+        let handler_storage = storage.clone();
+        let mut router = HelixRouter::new();
+        router.add_pattern_route(
+            Method::Patch,
+            "/node/:id",
+            Arc::new(move |input: &HandlerInput| -> Result<Response, RouterError> {
+                let id = input.param_str("id").unwrap_or("").to_string();
+                let patch: HashMap<String, Value> = serde_json::from_slice(&input.request.body)
+                    .map_err(|e| RouterError::HandlerError(e.to_string()))?;
+                let patched = handler_storage.patch_node(&id, patch)?;
+                Ok(Response::ok(serde_json::to_vec(&patched).unwrap_or_default()))
+            }),
+        );
+
+        let mut request = Request::new(Method::Patch, format!("/node/{}", node.id));
+        let mut patch = HashMap::new();
+        patch.insert("name".to_string(), Value::String("Grace".to_string()));
+        patch.insert("age".to_string(), Value::Integer(30));
+        patch.insert("bio".to_string(), Value::Empty);
+        request.body = serde_json::to_vec(&patch).unwrap();
+
+        let resp = router.dispatch(request).unwrap();
+        let patched: crate::protocol::Node = serde_json::from_slice(&resp.body).unwrap();
+
+        assert_eq!(patched.properties.get("name"), Some(&Value::String("Grace".to_string())));
+        assert_eq!(patched.properties.get("age"), Some(&Value::Integer(30)));
+        assert!(!patched.properties.contains_key("bio"));
+
+        let reloaded = storage.get_node(&node.id).unwrap();
+        assert_eq!(reloaded.properties.get("name"), Some(&Value::String("Grace".to_string())));
+    }
+}
@@ -0,0 +1,562 @@
+use crate::helix_gateway::router::HelixRouter;
+use crate::protocol::request::RequestParseError;
+use crate::protocol::{Request, Response};
+use std::collections::HashMap;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tunables affecting how a worker serves one accepted connection.
+#[derive(Debug, Clone, Copy)]
+pub struct GatewayOpts {
+    /// How long a connection can sit without sending a full request before
+    /// the worker gives up on it and moves on. Defaults to 30 seconds, so a
+    /// client that opens a connection and sends nothing can't tie up a
+    /// worker forever.
+    pub read_timeout: Duration,
+}
+
+impl Default for GatewayOpts {
+    fn default() -> Self {
+        GatewayOpts {
+            read_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A connection `handle_connection` can serve: `Read`/`Write` for the
+/// protocol itself, plus a way to bound how long a read can block. Real
+/// sockets ([`TcpStream`]) enforce the bound; in-memory test doubles that
+/// never actually block can rely on the no-op default.
+pub trait ConnectionStream: Read + Write {
+    fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl ConnectionStream for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+impl<S: ConnectionStream + ?Sized> ConnectionStream for &mut S {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+/// Serves every request on one connection, reusing a single `BufReader` for
+/// the lifetime of the connection instead of allocating a fresh buffer per
+/// request.
+///
+/// Sets `opts.read_timeout` on `stream` before reading anything, so a
+/// connection that never sends a request (or goes idle between keep-alive
+/// requests for too long) surfaces as [`RequestParseError::TimedOut`] and is
+/// dropped instead of blocking this worker indefinitely.
+pub fn handle_connection<S: ConnectionStream>(stream: S, router: &HelixRouter, opts: &GatewayOpts) {
+    let _ = stream.set_read_timeout(Some(opts.read_timeout));
+    let mut reader = BufReader::new(stream);
+    loop {
+        let request = match Request::from_stream(&mut reader) {
+            Ok(request) => request,
+            Err(RequestParseError::ConnectionClosed) | Err(RequestParseError::TimedOut) => return,
+            // A malformed request line/headers/body never reaches the
+            // router — it's answered with a 400 directly instead of being
+            // fabricated into a bogus `GET /`.
+            Err(_) => {
+                let _ = write_response(reader.get_mut(), &Response::bad_request(b"bad request".to_vec()));
+                return;
+            }
+        };
+        let close_after = wants_connection_close(&request.headers);
+
+        let response = match router.dispatch(request) {
+            Ok(response) => response,
+            Err(_) => Response::not_found(),
+        };
+
+        if write_response(reader.get_mut(), &response).is_err() || close_after {
+            return;
+        }
+    }
+}
+
+/// A connection id paired with its `last_active` timestamp, shared between
+/// whatever accepts connections and [`handle_tracked_connection`].
+///
+/// This gateway accepts and serves each connection directly — there's no
+/// channel-fed worker pool here for a reaper thread to watch, so nothing in
+/// this crate reaps idle connections yet — but [`handle_tracked_connection`]
+/// keeps `last_active` current for every connection it's given an id for,
+/// so one can be added later without threading anything else through the
+/// connection lifecycle.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionTracker {
+    last_active: Arc<Mutex<HashMap<u64, Instant>>>,
+}
+
+impl ConnectionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that connection `id` just served a request.
+    pub fn touch(&self, id: u64) {
+        self.last_active.lock().unwrap().insert(id, Instant::now());
+    }
+
+    /// The timestamp of connection `id`'s most recent request, if it's
+    /// served one and hasn't since been [`ConnectionTracker::remove`]d.
+    pub fn last_active(&self, id: u64) -> Option<Instant> {
+        self.last_active.lock().unwrap().get(&id).copied()
+    }
+
+    /// Drops `id`'s entry, e.g. once its connection has closed.
+    pub fn remove(&self, id: u64) {
+        self.last_active.lock().unwrap().remove(&id);
+    }
+}
+
+/// A stream paired with the connection id [`handle_tracked_connection`]
+/// reports back to a [`ConnectionTracker`] as it serves requests. The id is
+/// the caller's to assign — a monotonic counter or the connection's fd both
+/// work — `handle_tracked_connection` never inspects it beyond using it as
+/// a map key.
+pub struct TrackedConnection<S> {
+    pub id: u64,
+    pub stream: S,
+}
+
+/// Identical to [`handle_connection`], except it calls
+/// [`ConnectionTracker::touch`] with `conn.id` after every request it
+/// serves, so `tracker` reflects when each tracked connection was last
+/// heard from. Doesn't remove `id` from `tracker` on disconnect — that's
+/// left to the caller (or a future reaper), since `handle_tracked_connection`
+/// doesn't otherwise know whether `id` is still meaningful to anyone else.
+pub fn handle_tracked_connection<S: ConnectionStream>(
+    conn: TrackedConnection<S>,
+    router: &HelixRouter,
+    opts: &GatewayOpts,
+    tracker: &ConnectionTracker,
+) {
+    let TrackedConnection { id, stream } = conn;
+    let _ = stream.set_read_timeout(Some(opts.read_timeout));
+    let mut reader = BufReader::new(stream);
+    loop {
+        let request = match Request::from_stream(&mut reader) {
+            Ok(request) => request,
+            Err(RequestParseError::ConnectionClosed) | Err(RequestParseError::TimedOut) => return,
+            Err(_) => {
+                let _ = write_response(reader.get_mut(), &Response::bad_request(b"bad request".to_vec()));
+                return;
+            }
+        };
+        let close_after = wants_connection_close(&request.headers);
+
+        let response = match router.dispatch(request) {
+            Ok(response) => response,
+            Err(_) => Response::not_found(),
+        };
+
+        if write_response(reader.get_mut(), &response).is_err() {
+            return;
+        }
+
+        tracker.touch(id);
+        if close_after {
+            return;
+        }
+    }
+}
+
+/// Whether `headers` carries a `Connection: close` request, checked
+/// case-insensitively on both the header name and its value since neither is
+/// normalized by [`Request::from_stream`]. A connection that asks for this
+/// is served its response and then dropped instead of being kept open for
+/// another request.
+fn wants_connection_close(headers: &HashMap<String, String>) -> bool {
+    headers
+        .iter()
+        .any(|(k, v)| k.eq_ignore_ascii_case("connection") && v.eq_ignore_ascii_case("close"))
+}
+
+/// Caps how many connections [`handle_connection_with_limit`] will serve at
+/// once. `Clone` is cheap (an `Arc` underneath) so every thread accepting
+/// connections can hold its own copy of the same counter.
+///
+/// This gateway otherwise has no queue a burst of connections backs up
+/// behind — each is handled on its own thread for as long as it's open (see
+/// [`ConnectionTracker`]) — so without a cap like this one, enough slow
+/// handlers eventually exhaust OS threads instead of giving the client any
+/// signal that the server is overloaded.
+#[derive(Debug, Clone)]
+pub struct AdmissionLimiter {
+    in_flight: Arc<std::sync::atomic::AtomicUsize>,
+    max: usize,
+}
+
+impl AdmissionLimiter {
+    pub fn new(max: usize) -> Self {
+        AdmissionLimiter {
+            in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Reserves one of `max` slots, returning a guard that frees it again on
+    /// drop, or `None` if every slot is already taken.
+    pub fn try_acquire(&self) -> Option<AdmissionGuard> {
+        use std::sync::atomic::Ordering;
+        loop {
+            let current = self.in_flight.load(Ordering::SeqCst);
+            if current >= self.max {
+                return None;
+            }
+            if self
+                .in_flight
+                .compare_exchange(current, current + 1, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return Some(AdmissionGuard {
+                    in_flight: self.in_flight.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+/// Releases its [`AdmissionLimiter`] slot when dropped, however the
+/// connection it was reserved for finishes (normal completion, error, or
+/// panic unwinding).
+pub struct AdmissionGuard {
+    in_flight: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl Drop for AdmissionGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Mirrors [`handle_connection`], but answers with `503 Service Unavailable`
+/// and drops the connection immediately instead of serving it when `limiter`
+/// is already at capacity — the caller's accept loop can call this
+/// unconditionally for every new connection rather than deciding itself
+/// whether there's room.
+pub fn handle_connection_with_limit<S: ConnectionStream>(
+    mut stream: S,
+    router: &HelixRouter,
+    opts: &GatewayOpts,
+    limiter: &AdmissionLimiter,
+) {
+    let Some(_guard) = limiter.try_acquire() else {
+        let _ = write_response(&mut stream, &Response::new(503, b"server busy".to_vec()));
+        return;
+    };
+    handle_connection(stream, router, opts);
+}
+
+fn write_response<W: Write>(writer: &mut W, response: &Response) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    write!(writer, "HTTP/1.1 {} \r\n", response.status)?;
+    for (key, value) in &response.headers {
+        write!(writer, "{key}: {value}\r\n")?;
+    }
+    write!(writer, "Content-Length: {}\r\n\r\n", response.body.len())?;
+    writer.write_all(&response.body)?;
+    writer.flush()
+}
+
+/// Writes `response`'s status and headers followed by `chunks` as an
+/// HTTP `Transfer-Encoding: chunked` body, so a large traversal result can be
+/// streamed out one chunk at a time instead of buffering the whole
+/// serialized response in memory first.
+///
+/// Each chunk is written as its length in hex, `\r\n`, the chunk bytes, then
+/// `\r\n`, terminated by the standard zero-length final chunk.
+pub fn write_chunked_response<W: Write>(
+    writer: &mut W,
+    response: &Response,
+    chunks: impl Iterator<Item = Vec<u8>>,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    write!(writer, "HTTP/1.1 {} \r\n", response.status)?;
+    for (key, value) in &response.headers {
+        write!(writer, "{key}: {value}\r\n")?;
+    }
+    write!(writer, "Transfer-Encoding: chunked\r\n\r\n")?;
+
+    for chunk in chunks {
+        if chunk.is_empty() {
+            continue;
+        }
+        write!(writer, "{:x}\r\n", chunk.len())?;
+        writer.write_all(&chunk)?;
+        write!(writer, "\r\n")?;
+    }
+    write!(writer, "0\r\n\r\n")?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Method;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    struct LoopbackStream {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Read for LoopbackStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for LoopbackStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl ConnectionStream for LoopbackStream {}
+
+    #[test]
+    fn handle_connection_writes_response_then_stops_on_closed_connection() {
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(|_input| Ok(Response::ok(b"ok".to_vec()))),
+        );
+
+        let stream = LoopbackStream {
+            input: Cursor::new(b"GET /health HTTP/1.1\r\n\r\n".to_vec()),
+            output: Vec::new(),
+        };
+        let mut stream = stream;
+        handle_connection(&mut stream, &router, &GatewayOpts::default());
+
+        assert!(String::from_utf8_lossy(&stream.output).contains("ok"));
+    }
+
+    #[test]
+    fn malformed_request_lines_get_a_400_and_never_reach_the_handler() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        for raw in [&b"\r\n\r\n"[..], &[0xff, 0xfe, 0x00, 0x01, b'\r', b'\n'][..]] {
+            let called = Arc::new(AtomicBool::new(false));
+            let called_in_handler = called.clone();
+            let mut router = HelixRouter::new();
+            router.add_route(
+                Method::Get,
+                "/",
+                Arc::new(move |_input| {
+                    called_in_handler.store(true, Ordering::SeqCst);
+                    Ok(Response::ok(b"ok".to_vec()))
+                }),
+            );
+
+            let mut stream = LoopbackStream {
+                input: Cursor::new(raw.to_vec()),
+                output: Vec::new(),
+            };
+            handle_connection(&mut stream, &router, &GatewayOpts::default());
+
+            assert!(!called.load(Ordering::SeqCst), "handler must not run for {raw:?}");
+            assert!(String::from_utf8_lossy(&stream.output).starts_with("HTTP/1.1 400"));
+        }
+    }
+
+    #[test]
+    fn admission_limiter_rejects_once_every_slot_is_taken_and_frees_on_drop() {
+        let limiter = AdmissionLimiter::new(2);
+
+        let first = limiter.try_acquire().unwrap();
+        let second = limiter.try_acquire().unwrap();
+        assert_eq!(limiter.in_flight(), 2);
+        assert!(limiter.try_acquire().is_none());
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(limiter.try_acquire().is_some());
+
+        drop(second);
+    }
+
+    #[test]
+    fn handle_connection_with_limit_answers_503_instead_of_serving_once_at_capacity() {
+        let called = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let called_in_handler = called.clone();
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(move |_input| {
+                called_in_handler.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Ok(Response::ok(b"ok".to_vec()))
+            }),
+        );
+
+        let limiter = AdmissionLimiter::new(1);
+        let _holding = limiter.try_acquire().unwrap();
+
+        let mut stream = LoopbackStream {
+            input: Cursor::new(b"GET /health HTTP/1.1\r\n\r\n".to_vec()),
+            output: Vec::new(),
+        };
+        handle_connection_with_limit(&mut stream, &router, &GatewayOpts::default(), &limiter);
+
+        assert_eq!(called.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert!(String::from_utf8_lossy(&stream.output).starts_with("HTTP/1.1 503"));
+    }
+
+    #[test]
+    fn chunked_response_reassembles_to_the_original_body() {
+        let mut output = Vec::new();
+        let chunks = vec![b"hello, ".to_vec(), b"chunked ".to_vec(), b"world".to_vec()];
+        write_chunked_response(&mut output, &Response::ok(Vec::new()), chunks.into_iter()).unwrap();
+
+        let text = String::from_utf8_lossy(&output);
+        let (headers, body) = text.split_once("\r\n\r\n").unwrap();
+        assert!(headers.contains("Transfer-Encoding: chunked"));
+
+        // Reassemble the chunked body the way a client would: read a hex
+        // length line, that many bytes, a trailing CRLF, repeat until the
+        // zero-length final chunk.
+        let mut reassembled = Vec::new();
+        let mut rest = body;
+        loop {
+            let (len_line, after_len) = rest.split_once("\r\n").unwrap();
+            let len = usize::from_str_radix(len_line, 16).unwrap();
+            if len == 0 {
+                break;
+            }
+            reassembled.extend_from_slice(&after_len.as_bytes()[..len]);
+            rest = &after_len[len + 2..];
+        }
+
+        assert_eq!(reassembled, b"hello, chunked world");
+    }
+
+    #[test]
+    fn handling_a_request_updates_the_tracker_last_active_for_that_connections_id() {
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(|_input| Ok(Response::ok(b"ok".to_vec()))),
+        );
+
+        let mut stream = LoopbackStream {
+            input: Cursor::new(b"GET /health HTTP/1.1\r\n\r\n".to_vec()),
+            output: Vec::new(),
+        };
+        let tracker = ConnectionTracker::new();
+        assert!(tracker.last_active(7).is_none());
+
+        let conn = TrackedConnection { id: 7, stream: &mut stream };
+        handle_tracked_connection(conn, &router, &GatewayOpts::default(), &tracker);
+
+        assert!(tracker.last_active(7).is_some());
+        assert!(tracker.last_active(8).is_none());
+    }
+
+    #[test]
+    fn connection_close_header_stops_the_loop_after_one_request() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_in_handler = calls.clone();
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(move |_input| {
+                calls_in_handler.fetch_add(1, Ordering::SeqCst);
+                Ok(Response::ok(b"ok".to_vec()))
+            }),
+        );
+
+        // Two complete, valid requests queued back to back, but the first
+        // asks for `Connection: close` — the second must never be served.
+        let mut stream = LoopbackStream {
+            input: Cursor::new(
+                b"GET /health HTTP/1.1\r\nConnection: close\r\n\r\nGET /health HTTP/1.1\r\n\r\n".to_vec(),
+            ),
+            output: Vec::new(),
+        };
+        handle_connection(&mut stream, &router, &GatewayOpts::default());
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn premature_eof_after_a_full_request_does_not_serve_a_bogus_second_request() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_in_handler = calls.clone();
+        let mut router = HelixRouter::new();
+        router.add_route(
+            Method::Get,
+            "/health",
+            Arc::new(move |_input| {
+                calls_in_handler.fetch_add(1, Ordering::SeqCst);
+                Ok(Response::ok(b"ok".to_vec()))
+            }),
+        );
+
+        // One complete request, then the connection drops mid-headers on
+        // the next one — that partial tail must never reach the router.
+        let mut stream = LoopbackStream {
+            input: Cursor::new(b"GET /health HTTP/1.1\r\n\r\nGET /health HTTP/1.1\r\nHost: x\r\n".to_vec()),
+            output: Vec::new(),
+        };
+        handle_connection(&mut stream, &router, &GatewayOpts::default());
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn idle_connection_is_dropped_after_the_read_timeout_frees_the_worker() {
+        use std::net::TcpListener;
+        use std::time::Instant;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let worker = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let router = HelixRouter::new();
+            let opts = GatewayOpts {
+                read_timeout: Duration::from_millis(100),
+            };
+            let start = Instant::now();
+            handle_connection(stream, &router, &opts);
+            start.elapsed()
+        });
+
+        // Connect but never send anything.
+        let _client = std::net::TcpStream::connect(addr).unwrap();
+
+        let elapsed = worker.join().unwrap();
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "worker should free up shortly after the read timeout elapses, took {elapsed:?}"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod helix_engine;
+pub mod helix_gateway;
+pub mod protocol;
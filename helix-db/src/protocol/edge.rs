@@ -0,0 +1,84 @@
+use super::value::Properties;
+use serde::{Deserialize, Serialize};
+
+/// A directed graph edge as stored in `CF_EDGES`.
+///
+/// The graph is a multigraph: `from_node`/`to_node`/`label` is not a unique
+/// key, there may be several edges between the same pair of nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edge {
+    pub id: String,
+    pub label: String,
+    pub from_node: String,
+    pub to_node: String,
+    pub properties: Properties,
+}
+
+impl Edge {
+    pub fn new(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        from_node: impl Into<String>,
+        to_node: impl Into<String>,
+        properties: Properties,
+    ) -> Self {
+        Edge {
+            id: id.into(),
+            label: label.into(),
+            from_node: from_node.into(),
+            to_node: to_node.into(),
+            properties,
+        }
+    }
+
+    /// Mirrors [`crate::protocol::Node::get_str`] over an edge's properties.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.properties.get(key)?.as_str()
+    }
+
+    /// Mirrors [`crate::protocol::Node::get_int`] over an edge's properties.
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.properties.get(key)?.as_int()
+    }
+
+    /// Mirrors [`crate::protocol::Node::get_float`] over an edge's properties.
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.properties.get(key)?.as_float()
+    }
+
+    /// Mirrors [`crate::protocol::Node::get_bool`] over an edge's properties.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.properties.get(key)?.as_bool()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Value;
+
+    #[test]
+    fn typed_accessors_return_the_value_only_for_a_present_matching_key() {
+        let mut properties = Properties::new();
+        properties.insert("since".to_string(), Value::Integer(2020));
+        properties.insert("weight".to_string(), Value::Float(0.75));
+        properties.insert("kind".to_string(), Value::String("knows".to_string()));
+        properties.insert("mutual".to_string(), Value::Boolean(false));
+        let edge = Edge::new("e1", "knows", "a", "b", properties);
+
+        assert_eq!(edge.get_int("since"), Some(2020));
+        assert_eq!(edge.get_float("weight"), Some(0.75));
+        assert_eq!(edge.get_str("kind"), Some("knows"));
+        assert_eq!(edge.get_bool("mutual"), Some(false));
+
+        // Wrong variant for the key.
+        assert_eq!(edge.get_str("since"), None);
+        assert_eq!(edge.get_bool("weight"), None);
+
+        // Missing key.
+        assert_eq!(edge.get_str("missing"), None);
+        assert_eq!(edge.get_int("missing"), None);
+        assert_eq!(edge.get_float("missing"), None);
+        assert_eq!(edge.get_bool("missing"), None);
+    }
+}
@@ -0,0 +1,11 @@
+pub mod edge;
+pub mod node;
+pub mod request;
+pub mod response;
+pub mod value;
+
+pub use edge::Edge;
+pub use node::Node;
+pub use request::{Method, Request};
+pub use response::Response;
+pub use value::{DataType, Properties, Value};
@@ -0,0 +1,78 @@
+use super::value::Properties;
+use serde::{Deserialize, Serialize};
+
+/// A graph node as stored in `CF_NODES` and returned from traversals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub label: String,
+    pub properties: Properties,
+}
+
+impl Node {
+    pub fn new(id: impl Into<String>, label: impl Into<String>, properties: Properties) -> Self {
+        Node {
+            id: id.into(),
+            label: label.into(),
+            properties,
+        }
+    }
+
+    /// `properties[key]` as a `&str`, or `None` if `key` is absent or holds
+    /// a different variant. Saves handlers the usual
+    /// `node.properties.get("age")` + `match Value::Integer` dance for the
+    /// common case of just wanting the typed value.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.properties.get(key)?.as_str()
+    }
+
+    /// `properties[key]` as an `i64`, or `None` if absent or a different
+    /// variant.
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.properties.get(key)?.as_int()
+    }
+
+    /// `properties[key]` as an `f64`, or `None` if absent or a different
+    /// variant.
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.properties.get(key)?.as_float()
+    }
+
+    /// `properties[key]` as a `bool`, or `None` if absent or a different
+    /// variant.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.properties.get(key)?.as_bool()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Value;
+
+    #[test]
+    fn typed_accessors_return_the_value_only_for_a_present_matching_key() {
+        let mut properties = Properties::new();
+        properties.insert("name".to_string(), Value::String("ada".to_string()));
+        properties.insert("age".to_string(), Value::Integer(30));
+        properties.insert("rating".to_string(), Value::Float(4.5));
+        properties.insert("active".to_string(), Value::Boolean(true));
+        let node = Node::new("n1", "person", properties);
+
+        assert_eq!(node.get_str("name"), Some("ada"));
+        assert_eq!(node.get_int("age"), Some(30));
+        assert_eq!(node.get_float("rating"), Some(4.5));
+        assert_eq!(node.get_bool("active"), Some(true));
+
+        // Wrong variant for the key.
+        assert_eq!(node.get_int("name"), None);
+        assert_eq!(node.get_str("age"), None);
+        assert_eq!(node.get_bool("rating"), None);
+
+        // Missing key.
+        assert_eq!(node.get_str("missing"), None);
+        assert_eq!(node.get_int("missing"), None);
+        assert_eq!(node.get_float("missing"), None);
+        assert_eq!(node.get_bool("missing"), None);
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, Read};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Patch => "PATCH",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for Method {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "GET" => Ok(Method::Get),
+            "POST" => Ok(Method::Post),
+            "PUT" => Ok(Method::Put),
+            "DELETE" => Ok(Method::Delete),
+            "PATCH" => Ok(Method::Patch),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A parsed HTTP-ish request as read off a gateway worker socket.
+///
+/// `path` never includes a `?query` suffix — it's split off and parsed into
+/// `query` during [`Request::from_stream`], so routing always matches on the
+/// bare path.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: Method,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// Percent-decodes `%XX` escapes and `+` (as a space) in a query string
+/// component. Bytes that don't form a valid `%XX` escape are passed through
+/// unchanged rather than rejected.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        out.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Parses a `key=value&key=value` query string into a map, URL-decoding
+/// both keys and values.
+fn parse_query_string(query: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let mut parts = pair.splitn(2, '=');
+        if let Some(key) = parts.next() {
+            let value = parts.next().unwrap_or("");
+            params.insert(percent_decode(key), percent_decode(value));
+        }
+    }
+    params
+}
+
+/// Why [`Request::from_stream`] couldn't produce a request.
+///
+/// `ConnectionClosed` is the expected, non-exceptional outcome when the
+/// client is done sending requests on a keep-alive connection or disconnects
+/// between requests — callers should treat it as "stop reading", not log it
+/// as an error.
+#[derive(Debug)]
+pub enum RequestParseError {
+    ConnectionClosed,
+    Malformed(String),
+    Io(String),
+    /// The underlying read didn't complete within the connection's read
+    /// timeout (see `GatewayOpts::read_timeout`) — typically a client that
+    /// opened a connection and then sent nothing. Callers should treat this
+    /// like `ConnectionClosed` and stop reading rather than retry.
+    TimedOut,
+}
+
+impl fmt::Display for RequestParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestParseError::ConnectionClosed => write!(f, "connection closed"),
+            RequestParseError::Malformed(msg) => write!(f, "malformed request: {msg}"),
+            RequestParseError::Io(msg) => write!(f, "io error: {msg}"),
+            RequestParseError::TimedOut => write!(f, "timed out waiting for data"),
+        }
+    }
+}
+
+impl std::error::Error for RequestParseError {}
+
+fn io_error_to_parse_error(e: std::io::Error) -> RequestParseError {
+    match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => RequestParseError::TimedOut,
+        _ => RequestParseError::Io(e.to_string()),
+    }
+}
+
+/// Strips a trailing `\r\n`/`\n` from `bytes` and validates what's left as
+/// UTF-8, rejecting with [`RequestParseError::Malformed`] instead of
+/// silently replacing invalid bytes — a request line or header is only ever
+/// ASCII tokens and Latin-1/UTF-8 header values in practice, so a non-UTF-8
+/// byte means a malformed client, not something to paper over and hand to a
+/// handler.
+fn ascii_line<'a>(bytes: &'a [u8], what: &str) -> Result<&'a str, RequestParseError> {
+    let mut end = bytes.len();
+    while end > 0 && matches!(bytes[end - 1], b'\r' | b'\n') {
+        end -= 1;
+    }
+    std::str::from_utf8(&bytes[..end])
+        .map_err(|_| RequestParseError::Malformed(format!("invalid UTF-8 in {what}")))
+}
+
+/// Reads a `Transfer-Encoding: chunked` body off `reader` and reassembles
+/// it into a single buffer, per-chunk size lines (hex, optionally followed
+/// by `;extension` which is ignored) and a trailing `0\r\n\r\n` terminator —
+/// no support for trailer headers after the terminator, since no caller in
+/// this codebase sends or expects them.
+fn read_chunked_body<R: BufRead + Read>(reader: &mut R) -> Result<Vec<u8>, RequestParseError> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = Vec::new();
+        let n = reader
+            .read_until(b'\n', &mut size_line)
+            .map_err(io_error_to_parse_error)?;
+        if n == 0 {
+            return Err(RequestParseError::ConnectionClosed);
+        }
+        let size_line = ascii_line(&size_line, "chunk size")?;
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| RequestParseError::Malformed(format!("invalid chunk size {size_hex:?}")))?;
+
+        if size == 0 {
+            let mut trailer = Vec::new();
+            reader
+                .read_until(b'\n', &mut trailer)
+                .map_err(io_error_to_parse_error)?;
+            break;
+        }
+
+        let mut chunk = vec![0u8; size];
+        reader.read_exact(&mut chunk).map_err(io_error_to_parse_error)?;
+        body.extend_from_slice(&chunk);
+
+        // Each chunk's data is followed by its own trailing `\r\n`.
+        let mut crlf = [0u8; 2];
+        reader.read_exact(&mut crlf).map_err(io_error_to_parse_error)?;
+    }
+    Ok(body)
+}
+
+impl Request {
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Request {
+            method,
+            path: path.into(),
+            query: HashMap::new(),
+            headers: HashMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Reads one request off `reader`. `reader` is expected to be a
+    /// long-lived, caller-owned `BufRead` (typically wrapping the
+    /// connection's `TcpStream`) so its internal buffer is reused across
+    /// requests on the same keep-alive connection instead of being
+    /// reallocated per call.
+    ///
+    /// An empty read (zero bytes for the request line) means the peer closed
+    /// the connection cleanly and is reported as [`RequestParseError::ConnectionClosed`],
+    /// not a panic — worker loops should stop reading on that connection
+    /// rather than unwrap.
+    pub fn from_stream<R: BufRead + Read>(reader: &mut R) -> Result<Request, RequestParseError> {
+        let mut line_bytes = Vec::new();
+        let bytes_read = reader
+            .read_until(b'\n', &mut line_bytes)
+            .map_err(io_error_to_parse_error)?;
+        if bytes_read == 0 {
+            return Err(RequestParseError::ConnectionClosed);
+        }
+        let line = ascii_line(&line_bytes, "request line")?;
+
+        let mut parts = line.splitn(3, ' ');
+        let method = parts
+            .next()
+            .ok_or_else(|| RequestParseError::Malformed("missing method".to_string()))?
+            .parse::<Method>()
+            .map_err(|_| RequestParseError::Malformed("unknown method".to_string()))?;
+        let raw_path = parts
+            .next()
+            .ok_or_else(|| RequestParseError::Malformed("missing path".to_string()))?;
+        let (path, query) = match raw_path.split_once('?') {
+            Some((path, query)) => (path.to_string(), parse_query_string(query)),
+            None => (raw_path.to_string(), HashMap::new()),
+        };
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut header_bytes = Vec::new();
+            let n = reader
+                .read_until(b'\n', &mut header_bytes)
+                .map_err(io_error_to_parse_error)?;
+            if n == 0 {
+                return Err(RequestParseError::ConnectionClosed);
+            }
+            let header_line = ascii_line(&header_bytes, "header")?;
+            if header_line.is_empty() {
+                break;
+            }
+            if let Some((key, value)) = header_line.split_once(':') {
+                headers.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        let is_chunked = headers
+            .get("Transfer-Encoding")
+            .map(|v| v.eq_ignore_ascii_case("chunked"))
+            .unwrap_or(false);
+
+        let body = if is_chunked {
+            read_chunked_body(reader)?
+        } else {
+            let content_length: usize = headers
+                .get("Content-Length")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0u8; content_length];
+            if content_length > 0 {
+                reader
+                    .read_exact(&mut body)
+                    .map_err(io_error_to_parse_error)?;
+            }
+            body
+        };
+
+        Ok(Request {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        })
+    }
+
+    /// Decodes `self.body` as `application/x-www-form-urlencoded`, the same
+    /// `key=value&key=value` shape a query string uses, reusing
+    /// [`parse_query_string`]'s percent-decoding. Doesn't check
+    /// `Content-Type` itself — [`crate::helix_gateway::router::HandlerInput::form_body`]
+    /// is the entry point that gates on it; this is the raw decode a caller
+    /// who already knows the body is form-encoded can call directly.
+    pub fn form_body(&self) -> HashMap<String, String> {
+        parse_query_string(&String::from_utf8_lossy(&self.body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn from_stream_parses_method_path_headers_and_body() {
+        let raw = b"POST /nodes HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
+        let mut reader = BufReader::new(&raw[..]);
+        let request = Request::from_stream(&mut reader).unwrap();
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.path, "/nodes");
+        assert_eq!(request.body, b"hello");
+    }
+
+    #[test]
+    fn from_stream_reassembles_a_chunked_body() {
+        let raw = b"POST /nodes HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world!\r\n0\r\n\r\n";
+        let mut reader = BufReader::new(&raw[..]);
+        let request = Request::from_stream(&mut reader).unwrap();
+        assert_eq!(request.body, b"hello world!");
+    }
+
+    #[test]
+    fn from_stream_reports_connection_closed_on_mid_request_eof() {
+        // Client sends the request line then disconnects before headers end.
+        let raw = b"GET /health HTTP/1.1\r\nHost: x\r\n";
+        let mut reader = BufReader::new(&raw[..]);
+        match Request::from_stream(&mut reader) {
+            Err(RequestParseError::ConnectionClosed) => {}
+            other => panic!("expected ConnectionClosed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_stream_splits_query_string_and_url_decodes_values() {
+        let raw = b"GET /search?q=hello%20world&limit=5 HTTP/1.1\r\n\r\n";
+        let mut reader = BufReader::new(&raw[..]);
+        let request = Request::from_stream(&mut reader).unwrap();
+        assert_eq!(request.path, "/search");
+        assert_eq!(request.query.get("q").map(String::as_str), Some("hello world"));
+        assert_eq!(request.query.get("limit").map(String::as_str), Some("5"));
+    }
+
+    #[test]
+    fn from_stream_reports_timed_out_when_the_underlying_read_would_block() {
+        struct AlwaysTimesOut;
+        impl Read for AlwaysTimesOut {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(std::io::ErrorKind::WouldBlock, "timed out"))
+            }
+        }
+
+        let mut reader = BufReader::new(AlwaysTimesOut);
+        match Request::from_stream(&mut reader) {
+            Err(RequestParseError::TimedOut) => {}
+            other => panic!("expected TimedOut, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_stream_rejects_a_header_with_invalid_utf8_instead_of_replacing_it() {
+        let mut raw = b"GET /health HTTP/1.1\r\nX-Bad: ".to_vec();
+        raw.push(0xFF);
+        raw.extend_from_slice(b"\r\n\r\n");
+        let mut reader = BufReader::new(&raw[..]);
+        match Request::from_stream(&mut reader) {
+            Err(RequestParseError::Malformed(msg)) => assert!(msg.contains("header")),
+            other => panic!("expected Malformed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_stream_reports_connection_closed_on_empty_stream() {
+        let raw: &[u8] = b"";
+        let mut reader = BufReader::new(raw);
+        match Request::from_stream(&mut reader) {
+            Err(RequestParseError::ConnectionClosed) => {}
+            other => panic!("expected ConnectionClosed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn form_body_decodes_key_value_pairs_and_percent_encoded_values() {
+        let mut request = Request::new(Method::Post, "/nodes");
+        request.body = b"label=person&name=Will%20Smith".to_vec();
+
+        let form = request.form_body();
+
+        assert_eq!(form.get("label"), Some(&"person".to_string()));
+        assert_eq!(form.get("name"), Some(&"Will Smith".to_string()));
+    }
+}
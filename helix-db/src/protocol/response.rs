@@ -0,0 +1,136 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// Envelope body for [`Response::paginated`].
+#[derive(Debug, Serialize)]
+struct PaginatedBody<T> {
+    data: Vec<T>,
+    total: usize,
+    offset: usize,
+    has_more: bool,
+}
+
+/// A response written back to the gateway worker's socket.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "text/plain".to_string());
+        Response {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub fn ok(body: Vec<u8>) -> Self {
+        Response::new(200, body)
+    }
+
+    pub fn not_found() -> Self {
+        Response::new(404, b"not found".to_vec())
+    }
+
+    pub fn bad_request(body: impl Into<Vec<u8>>) -> Self {
+        Response::new(400, body.into())
+    }
+
+    /// Builds a JSON response for a `range`-paginated traversal: wraps
+    /// `data` (the already-sliced page) in `{data, total, offset, has_more}`
+    /// and also sets `X-Total-Count` to `total`, so a client that only reads
+    /// headers doesn't need to parse the body to know whether more pages
+    /// remain. `total` is the pre-slice count — see
+    /// [`crate::helix_engine::graph_core::TraversalBuilder::total_before_range`].
+    pub fn paginated<T: Serialize>(data: Vec<T>, total: usize, offset: usize) -> Response {
+        let has_more = offset + data.len() < total;
+        let body = PaginatedBody {
+            data,
+            total,
+            offset,
+            has_more,
+        };
+        let bytes = serde_json::to_vec(&body).unwrap_or_default();
+        let mut response = Response::new(200, bytes);
+        response
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        response.headers.insert("X-Total-Count".to_string(), total.to_string());
+        response
+    }
+
+    /// Serializes `data` according to the caller's `Accept` header: JSON if
+    /// it includes `application/json`, otherwise a human-readable text
+    /// summary (`Debug` formatting) with `Content-Type: text/plain`.
+    pub fn negotiated<T: Serialize + Debug>(accept: Option<&str>, data: &T) -> Response {
+        let wants_json = accept
+            .map(|a| a.contains("application/json"))
+            .unwrap_or(false);
+
+        if wants_json {
+            let body = serde_json::to_vec(data).unwrap_or_default();
+            let mut response = Response::new(200, body);
+            response
+                .headers
+                .insert("Content-Type".to_string(), "application/json".to_string());
+            response
+        } else {
+            let body = format!("{data:?}").into_bytes();
+            Response::new(200, body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize)]
+    struct Sample {
+        name: String,
+    }
+
+    #[test]
+    fn negotiated_prefers_json_when_accepted() {
+        let data = Sample { name: "a".to_string() };
+        let response = Response::negotiated(Some("text/html,application/json;q=0.9"), &data);
+        assert_eq!(response.headers.get("Content-Type").unwrap(), "application/json");
+        assert_eq!(response.body, br#"{"name":"a"}"#);
+    }
+
+    #[test]
+    fn negotiated_falls_back_to_text_summary() {
+        let data = Sample { name: "a".to_string() };
+        let response = Response::negotiated(Some("text/plain"), &data);
+        assert_eq!(response.headers.get("Content-Type").unwrap(), "text/plain");
+        assert_eq!(response.body, b"Sample { name: \"a\" }");
+    }
+
+    #[test]
+    fn paginated_reports_total_and_has_more_when_pages_remain() {
+        let page: Vec<u32> = (0..5).collect();
+        let response = Response::paginated(page, 25, 10);
+
+        assert_eq!(response.headers.get("X-Total-Count").unwrap(), "25");
+        let parsed: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(parsed["total"], 25);
+        assert_eq!(parsed["offset"], 10);
+        assert_eq!(parsed["has_more"], true);
+        assert_eq!(parsed["data"].as_array().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn paginated_reports_has_more_false_on_the_last_page() {
+        let page: Vec<u32> = (0..5).collect();
+        let response = Response::paginated(page, 20, 15);
+
+        let parsed: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(parsed["has_more"], false);
+    }
+}
@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A single property value stored on a node or edge.
+///
+/// `Value` is the on-disk and over-the-wire representation for anything a
+/// user can attach to a graph element. New variants should keep `PartialEq`
+/// derivable and stay cheap to clone where possible.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Array(Vec<Value>),
+    /// Raw binary data (embeddings, thumbnails, hashes, ...). Serializes as
+    /// base64 for human-readable formats (JSON responses) and as raw bytes
+    /// for bincode storage. Ordering/filter steps treat `Bytes` as
+    /// unorderable: comparisons against it are always `false`/skip the value
+    /// rather than erroring, the same way `Array` is treated today.
+    #[serde(with = "bytes_as_base64")]
+    Bytes(Vec<u8>),
+    Empty,
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Self {
+        Value::Bytes(bytes)
+    }
+}
+
+mod bytes_as_base64 {
+    use serde::de::{Error as DeError, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S>(bytes: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64_encode(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a base64 string or a byte buffer")
+            }
+
+            fn visit_str<E: DeError>(self, v: &str) -> Result<Vec<u8>, E> {
+                base64_decode(v).map_err(DeError::custom)
+            }
+
+            fn visit_bytes<E: DeError>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: DeError>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+                Ok(v)
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    fn base64_encode(bytes: &[u8]) -> String {
+        let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            out.push(ALPHABET[(b[0] >> 2) as usize] as char);
+            out.push(ALPHABET[(((b[0] & 0x03) << 4) | (b[1] >> 4)) as usize] as char);
+            out.push(if chunk.len() > 1 {
+                ALPHABET[(((b[1] & 0x0f) << 2) | (b[2] >> 6)) as usize] as char
+            } else {
+                '='
+            });
+            out.push(if chunk.len() > 2 {
+                ALPHABET[(b[2] & 0x3f) as usize] as char
+            } else {
+                '='
+            });
+        }
+        out
+    }
+
+    fn base64_decode(s: &str) -> Result<Vec<u8>, String> {
+        let s = s.trim_end_matches('=');
+        let mut out = Vec::with_capacity(s.len() * 3 / 4);
+        let mut buf = 0u32;
+        let mut bits = 0u32;
+        for c in s.bytes() {
+            let val = ALPHABET
+                .iter()
+                .position(|&b| b == c)
+                .ok_or_else(|| format!("invalid base64 byte: {c}"))? as u32;
+            buf = (buf << 6) | val;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((buf >> bits) as u8);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::Integer(i)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+/// Target variant for [`Value::coerce`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+impl Value {
+    /// The string this value holds, or `None` if it's any other variant.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The integer this value holds, or `None` if it's any other variant —
+    /// including `Float`, since that conversion would silently lose
+    /// precision rather than fail loudly.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// The float this value holds, or `None` if it's any other variant.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// The boolean this value holds, or `None` if it's any other variant.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Orders two values for comparison-based filters and sorts: numbers
+    /// compare numerically (mixing `Integer` and `Float` is fine), strings
+    /// compare lexically, and booleans order `false < true`. Returns `None`
+    /// for any other pairing (including `Array`/`Bytes`/`Empty` on either
+    /// side, or comparing across unrelated variants), so callers like
+    /// `where_gt` can treat an incomparable value as "doesn't match" rather
+    /// than guessing at an ordering.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Converts this value to `to`, or `None` if the conversion isn't
+    /// sensible (e.g. `String("not a number")` to `Integer`, or `Array`/
+    /// `Bytes`/`Empty` to anything). Already being the target variant
+    /// always succeeds and returns a clone of `self`.
+    pub fn coerce(&self, to: DataType) -> Option<Value> {
+        match (self, to) {
+            (Value::String(_), DataType::String)
+            | (Value::Integer(_), DataType::Integer)
+            | (Value::Float(_), DataType::Float)
+            | (Value::Boolean(_), DataType::Boolean) => Some(self.clone()),
+
+            (Value::String(s), DataType::Integer) => s.parse::<i64>().ok().map(Value::Integer),
+            (Value::Float(f), DataType::Integer) => Some(Value::Integer(*f as i64)),
+            (Value::Boolean(b), DataType::Integer) => Some(Value::Integer(*b as i64)),
+
+            (Value::String(s), DataType::Float) => s.parse::<f64>().ok().map(Value::Float),
+            (Value::Integer(i), DataType::Float) => Some(Value::Float(*i as f64)),
+
+            (Value::Integer(i), DataType::String) => Some(Value::String(i.to_string())),
+            (Value::Float(f), DataType::String) => Some(Value::String(f.to_string())),
+            (Value::Boolean(b), DataType::String) => Some(Value::String(b.to_string())),
+
+            (Value::String(s), DataType::Boolean) => match s.as_str() {
+                "true" => Some(Value::Boolean(true)),
+                "false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+}
+
+/// The property bag attached to a [`crate::protocol::node::Node`] or
+/// [`crate::protocol::edge::Edge`].
+pub type Properties = HashMap<String, Value>;
+
+/// Builds a `Vec<(String, Value)>` of `key: value` pairs, converting each
+/// value via `Value::from`. Useful anywhere pairs are wanted in the order
+/// they were written, e.g. asserting on a specific property order in a test.
+#[macro_export]
+macro_rules! props {
+    ($($key:ident : $value:expr),* $(,)?) => {
+        vec![$((stringify!($key).to_string(), $crate::protocol::Value::from($value))),*]
+    };
+}
+
+/// Sibling of [`props!`] that builds a [`Properties`] (`HashMap<String,
+/// Value>`) directly with the same `key: value` syntax, for the common case
+/// of handing a property bag straight to
+/// [`crate::helix_engine::storage_core::StorageMethods::create_node`]/
+/// `create_edge` without a manual `HashMap::from_iter(props![...])`.
+#[macro_export]
+macro_rules! props_map {
+    ($($key:ident : $value:expr),* $(,)?) => {
+        $crate::protocol::Properties::from_iter($crate::props![$($key : $value),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Node;
+
+    #[test]
+    fn bytes_round_trip_through_bincode_and_json() {
+        let mut properties = Properties::new();
+        properties.insert("embedding".to_string(), Value::Bytes(vec![0, 1, 2, 255, 254]));
+        let node = Node::new("n1", "doc", properties);
+
+        let bincode_bytes = bincode::serialize(&node).unwrap();
+        let from_bincode: Node = bincode::deserialize(&bincode_bytes).unwrap();
+        assert_eq!(
+            from_bincode.properties.get("embedding"),
+            Some(&Value::Bytes(vec![0, 1, 2, 255, 254]))
+        );
+
+        let json = serde_json::to_string(&node).unwrap();
+        assert!(json.contains("AAEC"), "expected base64 in JSON output: {json}");
+        let from_json: Node = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            from_json.properties.get("embedding"),
+            Some(&Value::Bytes(vec![0, 1, 2, 255, 254]))
+        );
+    }
+
+    #[test]
+    fn compare_orders_numbers_strings_and_booleans() {
+        assert_eq!(Value::Integer(1).compare(&Value::Integer(2)), Some(Ordering::Less));
+        assert_eq!(Value::Float(2.5).compare(&Value::Float(2.5)), Some(Ordering::Equal));
+        assert_eq!(Value::Integer(3).compare(&Value::Float(2.5)), Some(Ordering::Greater));
+        assert_eq!(Value::Float(1.5).compare(&Value::Integer(2)), Some(Ordering::Less));
+        assert_eq!(
+            Value::String("apple".to_string()).compare(&Value::String("banana".to_string())),
+            Some(Ordering::Less)
+        );
+        assert_eq!(Value::Boolean(false).compare(&Value::Boolean(true)), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn compare_returns_none_for_incomparable_pairs() {
+        assert_eq!(Value::Integer(1).compare(&Value::String("1".to_string())), None);
+        assert_eq!(Value::Boolean(true).compare(&Value::Integer(1)), None);
+        assert_eq!(Value::Array(vec![]).compare(&Value::Array(vec![])), None);
+        assert_eq!(Value::Bytes(vec![1]).compare(&Value::Bytes(vec![1])), None);
+        assert_eq!(Value::Empty.compare(&Value::Empty), None);
+    }
+
+    #[test]
+    fn props_builds_ordered_pairs_and_props_map_builds_a_hashmap() {
+        let pairs = crate::props! { name: "ada", age: 30 };
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0], ("name".to_string(), Value::String("ada".to_string())));
+        assert_eq!(pairs[1], ("age".to_string(), Value::Integer(30)));
+
+        let map = crate::props_map! { name: "ada", age: 30 };
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("name"), Some(&Value::String("ada".to_string())));
+        assert_eq!(map.get("age"), Some(&Value::Integer(30)));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod migration;
+pub mod schema;
+
+pub use migration::{diff_schemas, generate_migration, SchemaChange};
+pub use schema::{EdgeSchema, FieldSchema, NodeSchema, Source};
@@ -0,0 +1,264 @@
+use crate::schema::{FieldSchema, Source};
+
+/// A single difference between two versions of a schema, as reported by
+/// [`diff_schemas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    NodeAdded(String),
+    NodeRemoved(String),
+    EdgeAdded(String),
+    EdgeRemoved(String),
+    FieldAdded {
+        schema: String,
+        field: String,
+        type_name: String,
+    },
+    FieldRemoved {
+        schema: String,
+        field: String,
+    },
+    FieldTypeChanged {
+        schema: String,
+        field: String,
+        old_type: String,
+        new_type: String,
+    },
+}
+
+/// Compares two schema snapshots and reports every added/removed node and
+/// edge schema, plus added/removed/changed fields on schemas present in both.
+pub fn diff_schemas(old: &Source, new: &Source) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    diff_group(
+        &old.nodes.iter().map(|n| (n.name.clone(), n.fields.clone())).collect::<Vec<_>>(),
+        &new.nodes.iter().map(|n| (n.name.clone(), n.fields.clone())).collect::<Vec<_>>(),
+        SchemaChange::NodeAdded,
+        SchemaChange::NodeRemoved,
+        &mut changes,
+    );
+
+    diff_group(
+        &old.edges.iter().map(|e| (e.name.clone(), e.fields.clone())).collect::<Vec<_>>(),
+        &new.edges.iter().map(|e| (e.name.clone(), e.fields.clone())).collect::<Vec<_>>(),
+        SchemaChange::EdgeAdded,
+        SchemaChange::EdgeRemoved,
+        &mut changes,
+    );
+
+    changes
+}
+
+fn diff_group(
+    old: &[(String, Vec<FieldSchema>)],
+    new: &[(String, Vec<FieldSchema>)],
+    added: impl Fn(String) -> SchemaChange,
+    removed: impl Fn(String) -> SchemaChange,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (name, new_fields) in new {
+        match old.iter().find(|(old_name, _)| old_name == name) {
+            Some((_, old_fields)) => diff_fields(name, old_fields, new_fields, changes),
+            None => changes.push(added(name.clone())),
+        }
+    }
+    for (name, _) in old {
+        if !new.iter().any(|(new_name, _)| new_name == name) {
+            changes.push(removed(name.clone()));
+        }
+    }
+}
+
+fn diff_fields(schema: &str, old: &[FieldSchema], new: &[FieldSchema], changes: &mut Vec<SchemaChange>) {
+    for field in new {
+        match old.iter().find(|f| f.name == field.name) {
+            Some(old_field) if old_field.type_name != field.type_name => {
+                changes.push(SchemaChange::FieldTypeChanged {
+                    schema: schema.to_string(),
+                    field: field.name.clone(),
+                    old_type: old_field.type_name.clone(),
+                    new_type: field.type_name.clone(),
+                });
+            }
+            Some(_) => {}
+            None => changes.push(SchemaChange::FieldAdded {
+                schema: schema.to_string(),
+                field: field.name.clone(),
+                type_name: field.type_name.clone(),
+            }),
+        }
+    }
+    for field in old {
+        if !new.iter().any(|f| f.name == field.name) {
+            changes.push(SchemaChange::FieldRemoved {
+                schema: schema.to_string(),
+                field: field.name.clone(),
+            });
+        }
+    }
+}
+
+/// Emits a Rust migration function stub covering `changes`. The stub is not
+/// meant to run unattended — `FieldAdded` becomes a loop backfilling a
+/// placeholder default that the operator is expected to replace, and
+/// removed/changed entries are left as comments flagging manual review.
+pub fn generate_migration(changes: &[SchemaChange]) -> String {
+    let mut out = String::new();
+    out.push_str("pub fn migrate(storage: &HelixGraphStorage) -> Result<(), GraphError> {\n");
+
+    if changes.is_empty() {
+        out.push_str("    // No schema changes detected.\n");
+    }
+
+    for change in changes {
+        match change {
+            SchemaChange::NodeAdded(name) => {
+                out.push_str(&format!("    // NODE {name} added: no backfill needed, new nodes start empty.\n"));
+            }
+            SchemaChange::NodeRemoved(name) => {
+                out.push_str(&format!("    // NODE {name} removed: review whether existing {name} nodes should be dropped.\n"));
+            }
+            SchemaChange::EdgeAdded(name) => {
+                out.push_str(&format!("    // EDGE {name} added: no backfill needed, new edges start empty.\n"));
+            }
+            SchemaChange::EdgeRemoved(name) => {
+                out.push_str(&format!("    // EDGE {name} removed: review whether existing {name} edges should be dropped.\n"));
+            }
+            SchemaChange::FieldAdded {
+                schema,
+                field,
+                type_name,
+            } => {
+                out.push_str(&format!(
+                    "    for node in storage.get_all_nodes()?.into_iter().filter(|n| n.label == \"{schema}\") {{\n"
+                ));
+                out.push_str(&format!(
+                    "        // TODO: replace Value::Empty with the real default for {field}: {type_name},\n"
+                ));
+                out.push_str("        // then write it back once HelixGraphStorage exposes an update_node method.\n");
+                out.push_str(&format!(
+                    "        let mut properties = node.properties.clone();\n        properties.entry(\"{field}\".to_string()).or_insert(Value::Empty);\n"
+                ));
+                out.push_str("    }\n");
+            }
+            SchemaChange::FieldRemoved { schema, field } => {
+                out.push_str(&format!(
+                    "    // {schema}.{field} removed: review whether to strip it from existing records.\n"
+                ));
+            }
+            SchemaChange::FieldTypeChanged {
+                schema,
+                field,
+                old_type,
+                new_type,
+            } => {
+                out.push_str(&format!(
+                    "    // {schema}.{field} changed type {old_type} -> {new_type}: write a conversion here.\n"
+                ));
+            }
+        }
+    }
+
+    out.push_str("    Ok(())\n");
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{EdgeSchema, NodeSchema};
+
+    #[test]
+    fn added_property_reports_field_added() {
+        let old = Source {
+            nodes: vec![NodeSchema {
+                name: "User".to_string(),
+                fields: vec![FieldSchema::new("name", "String")],
+            }],
+            edges: Vec::new(),
+        };
+        let new = Source {
+            nodes: vec![NodeSchema {
+                name: "User".to_string(),
+                fields: vec![FieldSchema::new("name", "String"), FieldSchema::new("age", "Integer")],
+            }],
+            edges: Vec::new(),
+        };
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            changes,
+            vec![SchemaChange::FieldAdded {
+                schema: "User".to_string(),
+                field: "age".to_string(),
+                type_name: "Integer".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn removed_schema_reports_node_removed() {
+        let old = Source {
+            nodes: vec![NodeSchema {
+                name: "User".to_string(),
+                fields: Vec::new(),
+            }],
+            edges: vec![EdgeSchema {
+                name: "Follows".to_string(),
+                fields: Vec::new(),
+            }],
+        };
+        let new = Source {
+            nodes: Vec::new(),
+            edges: vec![EdgeSchema {
+                name: "Follows".to_string(),
+                fields: Vec::new(),
+            }],
+        };
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(changes, vec![SchemaChange::NodeRemoved("User".to_string())]);
+    }
+
+    #[test]
+    fn changed_field_type_reports_field_type_changed() {
+        let old = Source {
+            nodes: Vec::new(),
+            edges: vec![EdgeSchema {
+                name: "Follows".to_string(),
+                fields: vec![FieldSchema::new("since", "Integer")],
+            }],
+        };
+        let new = Source {
+            nodes: Vec::new(),
+            edges: vec![EdgeSchema {
+                name: "Follows".to_string(),
+                fields: vec![FieldSchema::new("since", "String")],
+            }],
+        };
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            changes,
+            vec![SchemaChange::FieldTypeChanged {
+                schema: "Follows".to_string(),
+                field: "since".to_string(),
+                old_type: "Integer".to_string(),
+                new_type: "String".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn generate_migration_emits_backfill_loop_for_added_field() {
+        let changes = vec![SchemaChange::FieldAdded {
+            schema: "User".to_string(),
+            field: "age".to_string(),
+            type_name: "Integer".to_string(),
+        }];
+        let stub = generate_migration(&changes);
+        assert!(stub.contains("n.label == \"User\""));
+        assert!(stub.contains("\"age\""));
+    }
+}
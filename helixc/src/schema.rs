@@ -0,0 +1,36 @@
+/// A single field declared on a node or edge schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub name: String,
+    pub type_name: String,
+}
+
+impl FieldSchema {
+    pub fn new(name: impl Into<String>, type_name: impl Into<String>) -> Self {
+        FieldSchema {
+            name: name.into(),
+            type_name: type_name.into(),
+        }
+    }
+}
+
+/// A `NODE` schema declaration: a label plus its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSchema {
+    pub name: String,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// An `EDGE` schema declaration: a label plus its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeSchema {
+    pub name: String,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// The full set of schema declarations parsed from a HelixQL source file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Source {
+    pub nodes: Vec<NodeSchema>,
+    pub edges: Vec<EdgeSchema>,
+}